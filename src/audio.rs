@@ -0,0 +1,118 @@
+//! Audio coding: continuously variable slope delta modulation (`cvsd`) and
+//! mu-law companding.
+
+use liquidizers_sys::*;
+
+use crate::{created, Complex32, Error, Result};
+
+/// Continuously variable slope delta codec (`cvsd`), one bit per sample.
+pub struct Cvsd {
+    q: cvsd,
+}
+
+unsafe impl Send for Cvsd {}
+
+impl Cvsd {
+    /// Creates a codec that adapts its step size after `num_bits` equal bits
+    /// in a row, scaling it by `zeta`, with pre-emphasis factor `alpha`.
+    pub fn create(num_bits: u32, zeta: f32, alpha: f32) -> Result<Cvsd> {
+        let q = created(unsafe { cvsd_create(num_bits, zeta, alpha) })?;
+        Ok(Cvsd { q })
+    }
+
+    pub fn encode(&mut self, sample: f32) -> bool {
+        unsafe { cvsd_encode(self.q, sample) != 0 }
+    }
+
+    pub fn decode(&mut self, bit: bool) -> f32 {
+        unsafe { cvsd_decode(self.q, bit as _) }
+    }
+
+    /// Encodes eight samples into one byte, first sample in the most
+    /// significant bit.
+    pub fn encode8(&mut self, audio: &[f32; 8]) -> Result<u8> {
+        let mut data = 0;
+        liquid_try!(unsafe { cvsd_encode8(self.q, audio.as_ptr() as *mut _, &mut data) });
+        Ok(data)
+    }
+
+    /// Decodes one byte into eight samples.
+    pub fn decode8(&mut self, data: u8) -> Result<[f32; 8]> {
+        let mut audio = [0.0; 8];
+        liquid_try!(unsafe { cvsd_decode8(self.q, data, audio.as_mut_ptr()) });
+        Ok(audio)
+    }
+
+    /// Encodes `audio` into packed bytes; fails with [`Error::EIRANGE`] unless
+    /// its length is a multiple of 8.
+    pub fn encode_block(&mut self, audio: &[f32]) -> Result<Vec<u8>> {
+        if !audio.len().is_multiple_of(8) {
+            return Err(Error::EIRANGE);
+        }
+        let mut data = vec![0; audio.len() / 8];
+        for (chunk, d) in audio.chunks_exact(8).zip(data.iter_mut()) {
+            liquid_try!(unsafe { cvsd_encode8(self.q, chunk.as_ptr() as *mut _, d) });
+        }
+        Ok(data)
+    }
+
+    /// Decodes packed bytes into eight samples each.
+    pub fn decode_block(&mut self, data: &[u8]) -> Result<Vec<f32>> {
+        let mut audio = vec![0.0; data.len() * 8];
+        for (&d, chunk) in data.iter().zip(audio.chunks_exact_mut(8)) {
+            liquid_try!(unsafe { cvsd_decode8(self.q, d, chunk.as_mut_ptr()) });
+        }
+        Ok(audio)
+    }
+}
+
+impl Drop for Cvsd {
+    fn drop(&mut self) {
+        unsafe { cvsd_destroy(self.q) };
+    }
+}
+
+fn check_mu(mu: f32) -> Result<()> {
+    if mu > 0.0 {
+        Ok(())
+    } else {
+        Err(Error::EICONFIG)
+    }
+}
+
+/// Mu-law compresses samples in `[-1, 1]`; fails with [`Error::EICONFIG`]
+/// unless `mu > 0` (255 for telephony).
+pub fn compress_mulaw(x: &[f32], mu: f32) -> Result<Vec<f32>> {
+    check_mu(mu)?;
+    Ok(x.iter()
+        .map(|&x| unsafe { liquidizers_sys::compress_mulaw(x, mu) })
+        .collect())
+}
+
+/// Inverse of [`compress_mulaw`].
+pub fn expand_mulaw(y: &[f32], mu: f32) -> Result<Vec<f32>> {
+    check_mu(mu)?;
+    Ok(y.iter()
+        .map(|&y| unsafe { liquidizers_sys::expand_mulaw(y, mu) })
+        .collect())
+}
+
+/// Compresses the real and imaginary parts separately.
+pub fn compress_mulaw_complex(x: &[Complex32], mu: f32) -> Result<Vec<Complex32>> {
+    check_mu(mu)?;
+    let mut y = vec![Complex32::default(); x.len()];
+    for (&x, y) in x.iter().zip(y.iter_mut()) {
+        liquid_try!(unsafe { compress_cf_mulaw(x, mu, y) });
+    }
+    Ok(y)
+}
+
+/// Inverse of [`compress_mulaw_complex`].
+pub fn expand_mulaw_complex(y: &[Complex32], mu: f32) -> Result<Vec<Complex32>> {
+    check_mu(mu)?;
+    let mut x = vec![Complex32::default(); y.len()];
+    for (&y, x) in y.iter().zip(x.iter_mut()) {
+        liquid_try!(unsafe { expand_cf_mulaw(y, mu, x) });
+    }
+    Ok(x)
+}
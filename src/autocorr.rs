@@ -0,0 +1,156 @@
+//! Delay-and-correlate autocorrelation (`autocorr_rrrf`/`autocorr_cccf`).
+
+use liquidizers_sys::*;
+
+use std::os::raw::{c_int, c_uint};
+
+use crate::{created, sealed, Complex32, Result};
+
+/// Sample types with liquid autocorrelator implementations: `f32`
+/// (`autocorr_rrrf`) and [`Complex32`] (`autocorr_cccf`).
+pub trait AutoCorrelatorSample: sealed::Sealed + Copy + Default {
+    #[doc(hidden)]
+    type AutoCorr;
+
+    #[doc(hidden)]
+    unsafe fn autocorr_create(window_size: c_uint, delay: c_uint) -> *mut Self::AutoCorr;
+    #[doc(hidden)]
+    unsafe fn autocorr_destroy(q: *mut Self::AutoCorr) -> c_int;
+    #[doc(hidden)]
+    unsafe fn autocorr_reset(q: *mut Self::AutoCorr) -> c_int;
+    #[doc(hidden)]
+    unsafe fn autocorr_push(q: *mut Self::AutoCorr, x: Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn autocorr_write(q: *mut Self::AutoCorr, x: *mut Self, n: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn autocorr_execute(q: *mut Self::AutoCorr, rxx: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn autocorr_execute_block(
+        q: *mut Self::AutoCorr,
+        x: *mut Self,
+        n: c_uint,
+        rxx: *mut Self,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn autocorr_get_energy(q: *mut Self::AutoCorr) -> f32;
+}
+
+macro_rules! impl_autocorrelator_sample {
+    (
+        $t:ty,
+        $ac:ty {
+            $create:ident, $destroy:ident, $reset:ident, $push:ident, $write:ident,
+            $execute:ident, $execute_block:ident, $get_energy:ident,
+        },
+    ) => {
+        impl AutoCorrelatorSample for $t {
+            type AutoCorr = $ac;
+
+            unsafe fn autocorr_create(window_size: c_uint, delay: c_uint) -> *mut $ac {
+                $create(window_size, delay)
+            }
+            unsafe fn autocorr_destroy(q: *mut $ac) -> c_int {
+                $destroy(q)
+            }
+            unsafe fn autocorr_reset(q: *mut $ac) -> c_int {
+                $reset(q)
+            }
+            unsafe fn autocorr_push(q: *mut $ac, x: $t) -> c_int {
+                $push(q, x)
+            }
+            unsafe fn autocorr_write(q: *mut $ac, x: *mut $t, n: c_uint) -> c_int {
+                $write(q, x, n)
+            }
+            unsafe fn autocorr_execute(q: *mut $ac, rxx: *mut $t) -> c_int {
+                $execute(q, rxx)
+            }
+            unsafe fn autocorr_execute_block(
+                q: *mut $ac,
+                x: *mut $t,
+                n: c_uint,
+                rxx: *mut $t,
+            ) -> c_int {
+                $execute_block(q, x, n, rxx)
+            }
+            unsafe fn autocorr_get_energy(q: *mut $ac) -> f32 {
+                $get_energy(q)
+            }
+        }
+    };
+}
+
+impl_autocorrelator_sample! {
+    f32,
+    autocorr_rrrf_s {
+        autocorr_rrrf_create, autocorr_rrrf_destroy, autocorr_rrrf_reset, autocorr_rrrf_push,
+        autocorr_rrrf_write, autocorr_rrrf_execute, autocorr_rrrf_execute_block,
+        autocorr_rrrf_get_energy,
+    },
+}
+
+impl_autocorrelator_sample! {
+    Complex32,
+    autocorr_cccf_s {
+        autocorr_cccf_create, autocorr_cccf_destroy, autocorr_cccf_reset, autocorr_cccf_push,
+        autocorr_cccf_write, autocorr_cccf_execute, autocorr_cccf_execute_block,
+        autocorr_cccf_get_energy,
+    },
+}
+
+/// Correlates a signal against a delayed copy of itself over a sliding window
+/// (`autocorr_rrrf`/`autocorr_cccf`).
+pub struct AutoCorrelator<T: AutoCorrelatorSample> {
+    q: *mut T::AutoCorr,
+}
+
+unsafe impl<T: AutoCorrelatorSample> Send for AutoCorrelator<T> {}
+
+impl<T: AutoCorrelatorSample> AutoCorrelator<T> {
+    /// Creates a correlator summing over `window_size` samples at lag `delay`.
+    pub fn create(window_size: u32, delay: u32) -> Result<AutoCorrelator<T>> {
+        let q = created(unsafe { T::autocorr_create(window_size, delay) })?;
+        Ok(AutoCorrelator { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::autocorr_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn push(&mut self, x: T) -> Result<()> {
+        liquid_try!(unsafe { T::autocorr_push(self.q, x) });
+        Ok(())
+    }
+
+    pub fn write(&mut self, x: &[T]) -> Result<()> {
+        liquid_try!(unsafe { T::autocorr_write(self.q, x.as_ptr() as *mut _, x.len() as _) });
+        Ok(())
+    }
+
+    /// Autocorrelation over the current window.
+    pub fn execute(&mut self) -> Result<T> {
+        let mut rxx = T::default();
+        liquid_try!(unsafe { T::autocorr_execute(self.q, &mut rxx) });
+        Ok(rxx)
+    }
+
+    /// Pushes each sample of `x` and returns the autocorrelation after each.
+    pub fn execute_block(&mut self, x: &[T]) -> Result<Vec<T>> {
+        let mut rxx = vec![T::default(); x.len()];
+        liquid_try!(unsafe {
+            T::autocorr_execute_block(self.q, x.as_ptr() as *mut _, x.len() as _, rxx.as_mut_ptr())
+        });
+        Ok(rxx)
+    }
+
+    /// Signal energy over the current window, for normalizing the output.
+    pub fn energy(&self) -> f32 {
+        unsafe { T::autocorr_get_energy(self.q) }
+    }
+}
+
+impl<T: AutoCorrelatorSample> Drop for AutoCorrelator<T> {
+    fn drop(&mut self) {
+        unsafe { T::autocorr_destroy(self.q) };
+    }
+}
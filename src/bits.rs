@@ -0,0 +1,273 @@
+//! Bit packing and shifting over byte arrays (`liquid_pack_*`,
+//! `liquid_unpack_*`, `liquid_*shift`), most significant bit first.
+
+use liquidizers_sys::*;
+
+use crate::{Error, Result};
+
+/// Packs one bit per input byte (zero or non-zero) into bytes. The last byte
+/// is zero-padded.
+pub fn pack_bytes(bits: &[u8]) -> Result<Vec<u8>> {
+    // liquid keeps only the least significant bit of each input byte
+    let mut bits: Vec<u8> = bits.iter().map(|&b| (b != 0) as u8).collect();
+    let mut out = vec![0; bits.len().div_ceil(8)];
+    let mut num_written = 0;
+    liquid_try!(unsafe {
+        liquid_pack_bytes(
+            bits.as_mut_ptr(),
+            bits.len() as _,
+            out.as_mut_ptr(),
+            out.len() as _,
+            &mut num_written,
+        )
+    });
+    out.truncate(num_written as usize);
+    Ok(out)
+}
+
+/// Unpacks each byte into eight bytes of one bit each.
+pub fn unpack_bytes(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut out = vec![0; bytes.len() * 8];
+    let mut num_written = 0;
+    liquid_try!(unsafe {
+        liquid_unpack_bytes(
+            bytes.as_ptr() as *mut _,
+            bytes.len() as _,
+            out.as_mut_ptr(),
+            out.len() as _,
+            &mut num_written,
+        )
+    });
+    out.truncate(num_written as usize);
+    Ok(out)
+}
+
+/// Regroups `input`, holding `in_bps` bits per byte, into `out_bps` bits per
+/// byte. The last output symbol is zero-padded.
+pub fn repack_bytes(input: &[u8], in_bps: u32, out_bps: u32) -> Result<Vec<u8>> {
+    if in_bps == 0 || in_bps > 8 || out_bps == 0 || out_bps > 8 {
+        return Err(Error::EIRANGE);
+    }
+    let num_bits = input.len() * in_bps as usize;
+    let mut out = vec![0; num_bits.div_ceil(out_bps as usize)];
+    let mut num_written = 0;
+    liquid_try!(unsafe {
+        liquid_repack_bytes(
+            input.as_ptr() as *mut _,
+            in_bps,
+            input.len() as _,
+            out.as_mut_ptr(),
+            out_bps,
+            out.len() as _,
+            &mut num_written,
+        )
+    });
+    out.truncate(num_written as usize);
+    Ok(out)
+}
+
+fn check_field(len: usize, k: usize, b: u32) -> Result<()> {
+    if b > 8 || k + b as usize > len * 8 {
+        Err(Error::EIRANGE)
+    } else {
+        Ok(())
+    }
+}
+
+/// Writes the low `b` bits of `sym` into `dst` starting at bit `k`; fails with
+/// [`Error::EIRANGE`] if `b > 8` or the field runs past the end of `dst`.
+pub fn pack_array(dst: &mut [u8], k: usize, b: u32, sym: u8) -> Result<()> {
+    check_field(dst.len(), k, b)?;
+    liquid_try!(unsafe { liquid_pack_array(dst.as_mut_ptr(), dst.len() as _, k as _, b, sym) });
+    Ok(())
+}
+
+/// Reads `b` bits of `src` starting at bit `k`; fails with [`Error::EIRANGE`]
+/// if `b > 8` or the field runs past the end of `src`.
+pub fn unpack_array(src: &[u8], k: usize, b: u32) -> Result<u8> {
+    check_field(src.len(), k, b)?;
+    let mut sym = 0;
+    liquid_try!(unsafe {
+        liquid_unpack_array(src.as_ptr() as *mut _, src.len() as _, k as _, b, &mut sym)
+    });
+    Ok(sym)
+}
+
+/// Hard-decides soft bits (`0` to `255`, split at `128`) into a symbol of
+/// `soft_bits.len()` bits; fails with [`Error::EIRANGE`] if there are more
+/// than 32.
+pub fn pack_soft_bits(soft_bits: &[u8]) -> Result<u32> {
+    if soft_bits.len() > 32 {
+        return Err(Error::EIRANGE);
+    }
+    let mut sym = 0;
+    liquid_try!(unsafe {
+        liquid_pack_soft_bits(soft_bits.as_ptr() as *mut _, soft_bits.len() as _, &mut sym)
+    });
+    Ok(sym)
+}
+
+/// Expands the low `bps` bits of `sym` into soft bits of `0` or `255`;
+/// fails with [`Error::EIRANGE`] if `bps > 32`.
+pub fn unpack_soft_bits(sym: u32, bps: u32) -> Result<Vec<u8>> {
+    if bps > 32 {
+        return Err(Error::EIRANGE);
+    }
+    let mut soft_bits = vec![0; bps as usize];
+    liquid_try!(unsafe { liquid_unpack_soft_bits(sym, bps, soft_bits.as_mut_ptr()) });
+    Ok(soft_bits)
+}
+
+macro_rules! shift_fns {
+    ($($(#[$attr:meta])* $name:ident => $raw:ident,)*) => {
+        $(
+            $(#[$attr])*
+            pub fn $name(src: &mut [u8], b: u32) -> Result<()> {
+                liquid_try!(unsafe { $raw(src.as_mut_ptr(), src.len() as _, b) });
+                Ok(())
+            }
+        )*
+    };
+}
+
+shift_fns! {
+    /// Shifts the whole array left by `b` bits, `b < 8`, filling with zeros.
+    lbshift => liquid_lbshift,
+    /// Shifts the whole array right by `b` bits, `b < 8`, filling with zeros.
+    rbshift => liquid_rbshift,
+    /// Rotates the whole array left by `b` bits, `b < 8`.
+    lbcircshift => liquid_lbcircshift,
+    /// Rotates the whole array right by `b` bits, `b < 8`.
+    rbcircshift => liquid_rbcircshift,
+    /// Shifts the array left by `b` bytes, filling with zeros.
+    lshift => liquid_lshift,
+    /// Shifts the array right by `b` bytes, filling with zeros.
+    rshift => liquid_rshift,
+    /// Rotates the array left by `b` bytes.
+    lcircshift => liquid_lcircshift,
+    /// Rotates the array right by `b` bytes.
+    rcircshift => liquid_rcircshift,
+}
+
+/// Appends symbols of arbitrary width to a byte buffer, most significant bit
+/// first.
+#[derive(Clone, Debug, Default)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    num_bits: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> BitWriter {
+        BitWriter::default()
+    }
+
+    /// Appends the low `bps` bits of `sym`; fails with [`Error::EIRANGE`] if
+    /// `bps > 32`.
+    pub fn write(&mut self, sym: u32, bps: u32) -> Result<()> {
+        if bps > 32 {
+            return Err(Error::EIRANGE);
+        }
+        let mut remaining = bps;
+        while remaining > 0 {
+            let b = remaining.min(8);
+            remaining -= b;
+            let chunk = (sym >> remaining) & ((1 << b) - 1);
+            self.bytes
+                .resize((self.num_bits + b as usize).div_ceil(8), 0);
+            pack_array(&mut self.bytes, self.num_bits, b, chunk as u8)?;
+            self.num_bits += b as usize;
+        }
+        Ok(())
+    }
+
+    /// Number of bits written so far.
+    pub fn num_bits(&self) -> usize {
+        self.num_bits
+    }
+
+    /// Bytes written so far; the last one is zero-padded.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Reads symbols of arbitrary width from a byte slice, most significant bit
+/// first.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> BitReader<'a> {
+        BitReader { bytes, position: 0 }
+    }
+
+    /// Reads the next `bps` bits; fails with [`Error::EIRANGE`] if `bps > 32`
+    /// or fewer than `bps` bits remain.
+    pub fn read(&mut self, bps: u32) -> Result<u32> {
+        if bps > 32 || bps as usize > self.remaining() {
+            return Err(Error::EIRANGE);
+        }
+        let mut sym = 0;
+        let mut remaining = bps;
+        while remaining > 0 {
+            let b = remaining.min(8);
+            remaining -= b;
+            let chunk = unpack_array(self.bytes, self.position, b)?;
+            sym = (sym << b) | chunk as u32;
+            self.position += b as usize;
+        }
+        Ok(sym)
+    }
+
+    /// Bit offset of the next read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of bits left to read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.position
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_writer_reader_round_trip() {
+        // 47 bits in all, so the last byte is only partly used
+        let fields = [
+            (0b101, 3),
+            (0xdead_beef, 32),
+            (0, 1),
+            (0x5a, 7),
+            (0b1, 1),
+            (0b110, 3),
+        ];
+        let mut writer = BitWriter::new();
+        for &(sym, bps) in &fields {
+            writer.write(sym, bps).unwrap();
+        }
+        assert_eq!(writer.num_bits(), 47);
+        assert_eq!(writer.as_bytes().len(), 6);
+        assert_eq!(writer.as_bytes()[5] & 0x01, 0);
+
+        let bytes = writer.into_bytes();
+        let mut reader = BitReader::new(&bytes);
+        for &(sym, bps) in &fields {
+            assert_eq!(reader.read(bps).unwrap(), sym);
+        }
+        assert_eq!(reader.position(), 47);
+        assert_eq!(reader.remaining(), 1);
+        assert_eq!(reader.read(2), Err(Error::EIRANGE));
+    }
+}
@@ -0,0 +1,215 @@
+//! Byte-oriented packets: `bpacketgen`/`bpacketsync`.
+
+use liquidizers_sys::*;
+
+use std::collections::vec_deque::{self, VecDeque};
+use std::os::raw::{c_int, c_uchar, c_uint, c_void};
+use std::slice;
+
+use crate::{created, CrcScheme, Error, FecScheme, FrameSyncStats, Result};
+
+/// Wraps a message in a preamble, CRC and FEC for transmission as raw bytes (`bpacketgen`).
+pub struct BPacketGen {
+    q: bpacketgen,
+    msg_len: usize,
+}
+
+unsafe impl Send for BPacketGen {}
+
+impl BPacketGen {
+    /// Creates a generator for `msg_len`-byte messages; `m` is liquid's
+    /// preamble sequence parameter (0 selects the default).
+    pub fn create(
+        m: u32,
+        msg_len: usize,
+        crc: CrcScheme,
+        fec0: FecScheme,
+        fec1: FecScheme,
+    ) -> Result<BPacketGen> {
+        let q = created(unsafe {
+            bpacketgen_create(
+                m,
+                msg_len as _,
+                crc.to_raw() as _,
+                fec0.to_raw() as _,
+                fec1.to_raw() as _,
+            )
+        })?;
+        Ok(BPacketGen { q, msg_len })
+    }
+
+    /// Reconfigures the generator in place.
+    pub fn recreate(
+        &mut self,
+        m: u32,
+        msg_len: usize,
+        crc: CrcScheme,
+        fec0: FecScheme,
+        fec1: FecScheme,
+    ) -> Result<()> {
+        self.q = created(unsafe {
+            bpacketgen_recreate(
+                self.q,
+                m,
+                msg_len as _,
+                crc.to_raw() as _,
+                fec0.to_raw() as _,
+                fec1.to_raw() as _,
+            )
+        })?;
+        self.msg_len = msg_len;
+        Ok(())
+    }
+
+    pub fn msg_len(&self) -> usize {
+        self.msg_len
+    }
+
+    /// Length in bytes of an encoded packet.
+    pub fn packet_len(&self) -> usize {
+        unsafe { bpacketgen_get_packet_len(self.q) as usize }
+    }
+
+    /// Encodes `msg` into a packet of `packet_len()` bytes; fails with
+    /// [`Error::EIRANGE`] unless `msg` is `msg_len()` bytes.
+    pub fn encode(&mut self, msg: &[u8]) -> Result<Vec<u8>> {
+        if msg.len() != self.msg_len {
+            return Err(Error::EIRANGE);
+        }
+        let mut packet = vec![0; self.packet_len()];
+        unsafe { bpacketgen_encode(self.q, msg.as_ptr() as *mut _, packet.as_mut_ptr()) };
+        Ok(packet)
+    }
+}
+
+impl Drop for BPacketGen {
+    fn drop(&mut self) {
+        unsafe { bpacketgen_destroy(self.q) };
+    }
+}
+
+/// A packet recovered by [`BPacketSync`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BPacket {
+    pub payload: Vec<u8>,
+    pub valid: bool,
+    pub stats: FrameSyncStats,
+}
+
+type Callback = Box<dyn FnMut(&[u8], bool, FrameSyncStats)>;
+
+struct BPacketCallback {
+    callback: Option<Callback>,
+    queue: VecDeque<BPacket>,
+}
+
+unsafe extern "C" fn bpacketsync_trampoline(
+    payload: *mut c_uchar,
+    payload_valid: c_int,
+    payload_len: c_uint,
+    stats: framesyncstats_s,
+    userdata: *mut c_void,
+) -> c_int {
+    let state = &mut *(userdata as *mut BPacketCallback);
+    let payload: &[u8] = if payload.is_null() {
+        &[]
+    } else {
+        slice::from_raw_parts(payload, payload_len as usize)
+    };
+    let valid = payload_valid != 0;
+    let stats = FrameSyncStats::from_raw(&stats);
+    match state.callback {
+        Some(ref mut callback) => callback(payload, valid, stats),
+        None => state.queue.push_back(BPacket {
+            payload: payload.to_vec(),
+            valid,
+            stats,
+        }),
+    }
+    0
+}
+
+/// Finds and decodes [`BPacketGen`] packets in a byte stream (`bpacketsync`).
+///
+/// Decoded packets go to the closure given to [`create`](Self::create), or are
+/// queued for [`packets`](Self::packets) when built with [`queued`](Self::queued).
+pub struct BPacketSync {
+    q: bpacketsync,
+    state: Box<BPacketCallback>,
+}
+
+impl BPacketSync {
+    /// Creates a synchronizer that calls `callback` with each packet's payload,
+    /// CRC status and statistics.
+    pub fn create<F>(m: u32, callback: F) -> Result<BPacketSync>
+    where
+        F: FnMut(&[u8], bool, FrameSyncStats) + 'static,
+    {
+        BPacketSync::with_state(
+            m,
+            BPacketCallback {
+                callback: Some(Box::new(callback)),
+                queue: VecDeque::new(),
+            },
+        )
+    }
+
+    /// Creates a synchronizer that queues decoded packets.
+    pub fn queued(m: u32) -> Result<BPacketSync> {
+        BPacketSync::with_state(
+            m,
+            BPacketCallback {
+                callback: None,
+                queue: VecDeque::new(),
+            },
+        )
+    }
+
+    fn with_state(m: u32, state: BPacketCallback) -> Result<BPacketSync> {
+        let mut state = Box::new(state);
+        let userdata = &mut *state as *mut BPacketCallback as *mut c_void;
+        let q = created(unsafe { bpacketsync_create(m, Some(bpacketsync_trampoline), userdata) })?;
+        Ok(BPacketSync { q, state })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { bpacketsync_reset(self.q) });
+        Ok(())
+    }
+
+    /// Pushes a block of received bytes through the synchronizer.
+    pub fn execute(&mut self, bytes: &[u8]) -> Result<()> {
+        liquid_try!(unsafe {
+            bpacketsync_execute(self.q, bytes.as_ptr() as *mut _, bytes.len() as _)
+        });
+        Ok(())
+    }
+
+    pub fn execute_byte(&mut self, byte: u8) -> Result<()> {
+        liquid_try!(unsafe { bpacketsync_execute_byte(self.q, byte) });
+        Ok(())
+    }
+
+    /// Pushes a `bps`-bit symbol through the synchronizer.
+    pub fn execute_sym(&mut self, sym: u8, bps: u32) -> Result<()> {
+        liquid_try!(unsafe { bpacketsync_execute_sym(self.q, sym, bps) });
+        Ok(())
+    }
+
+    pub fn execute_bit(&mut self, bit: bool) -> Result<()> {
+        liquid_try!(unsafe { bpacketsync_execute_bit(self.q, bit as _) });
+        Ok(())
+    }
+
+    /// Drains packets queued since the last call. Always empty when a callback
+    /// was given to [`create`](Self::create).
+    pub fn packets(&mut self) -> vec_deque::Drain<'_, BPacket> {
+        self.state.queue.drain(..)
+    }
+}
+
+impl Drop for BPacketSync {
+    fn drop(&mut self) {
+        unsafe { bpacketsync_destroy(self.q) };
+    }
+}
@@ -0,0 +1,573 @@
+//! Ring buffers and delay lines: `cbuffer`, `window` and `wdelay`.
+
+use liquidizers_sys::*;
+
+use std::os::raw::{c_int, c_uint};
+use std::{ptr, slice};
+
+use crate::{created, sealed, Complex32, Error, Result};
+
+/// Sample types with liquid buffer implementations: `f32` and [`Complex32`].
+pub trait BufferSample: sealed::Sealed + Copy + Default {
+    #[doc(hidden)]
+    type CBuffer;
+    #[doc(hidden)]
+    type Window;
+    #[doc(hidden)]
+    type WDelay;
+
+    #[doc(hidden)]
+    unsafe fn cbuffer_create_max(max_size: c_uint, max_read: c_uint) -> *mut Self::CBuffer;
+    #[doc(hidden)]
+    unsafe fn cbuffer_copy(q: *mut Self::CBuffer) -> *mut Self::CBuffer;
+    #[doc(hidden)]
+    unsafe fn cbuffer_destroy(q: *mut Self::CBuffer) -> c_int;
+    #[doc(hidden)]
+    unsafe fn cbuffer_reset(q: *mut Self::CBuffer) -> c_int;
+    #[doc(hidden)]
+    unsafe fn cbuffer_size(q: *mut Self::CBuffer) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn cbuffer_max_size(q: *mut Self::CBuffer) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn cbuffer_max_read(q: *mut Self::CBuffer) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn cbuffer_space_available(q: *mut Self::CBuffer) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn cbuffer_push(q: *mut Self::CBuffer, v: Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn cbuffer_write(q: *mut Self::CBuffer, v: *mut Self, n: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn cbuffer_pop(q: *mut Self::CBuffer, v: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn cbuffer_read(
+        q: *mut Self::CBuffer,
+        n: c_uint,
+        v: *mut *mut Self,
+        r: *mut c_uint,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn cbuffer_release(q: *mut Self::CBuffer, n: c_uint) -> c_int;
+
+    #[doc(hidden)]
+    unsafe fn window_create(n: c_uint) -> *mut Self::Window;
+    #[doc(hidden)]
+    unsafe fn window_recreate(q: *mut Self::Window, n: c_uint) -> *mut Self::Window;
+    #[doc(hidden)]
+    unsafe fn window_copy(q: *mut Self::Window) -> *mut Self::Window;
+    #[doc(hidden)]
+    unsafe fn window_destroy(q: *mut Self::Window) -> c_int;
+    #[doc(hidden)]
+    unsafe fn window_reset(q: *mut Self::Window) -> c_int;
+    #[doc(hidden)]
+    unsafe fn window_read(q: *mut Self::Window, v: *mut *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn window_index(q: *mut Self::Window, i: c_uint, v: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn window_push(q: *mut Self::Window, v: Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn window_write(q: *mut Self::Window, v: *mut Self, n: c_uint) -> c_int;
+
+    #[doc(hidden)]
+    unsafe fn wdelay_create(delay: c_uint) -> *mut Self::WDelay;
+    #[doc(hidden)]
+    unsafe fn wdelay_recreate(q: *mut Self::WDelay, delay: c_uint) -> *mut Self::WDelay;
+    #[doc(hidden)]
+    unsafe fn wdelay_copy(q: *mut Self::WDelay) -> *mut Self::WDelay;
+    #[doc(hidden)]
+    unsafe fn wdelay_destroy(q: *mut Self::WDelay) -> c_int;
+    #[doc(hidden)]
+    unsafe fn wdelay_reset(q: *mut Self::WDelay) -> c_int;
+    #[doc(hidden)]
+    unsafe fn wdelay_read(q: *mut Self::WDelay, v: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn wdelay_push(q: *mut Self::WDelay, v: Self) -> c_int;
+}
+
+macro_rules! impl_buffer_sample {
+    (
+        $t:ty,
+        cbuffer: $cb:ty {
+            $cb_create_max:ident, $cb_copy:ident, $cb_destroy:ident, $cb_reset:ident,
+            $cb_size:ident, $cb_max_size:ident, $cb_max_read:ident, $cb_space:ident,
+            $cb_push:ident, $cb_write:ident, $cb_pop:ident, $cb_read:ident, $cb_release:ident,
+        },
+        window: $win:ty {
+            $win_create:ident, $win_recreate:ident, $win_copy:ident, $win_destroy:ident,
+            $win_reset:ident, $win_read:ident, $win_index:ident, $win_push:ident,
+            $win_write:ident,
+        },
+        wdelay: $wd:ty {
+            $wd_create:ident, $wd_recreate:ident, $wd_copy:ident, $wd_destroy:ident,
+            $wd_reset:ident, $wd_read:ident, $wd_push:ident,
+        },
+    ) => {
+        impl BufferSample for $t {
+            type CBuffer = $cb;
+            type Window = $win;
+            type WDelay = $wd;
+
+            unsafe fn cbuffer_create_max(max_size: c_uint, max_read: c_uint) -> *mut $cb {
+                $cb_create_max(max_size, max_read)
+            }
+            unsafe fn cbuffer_copy(q: *mut $cb) -> *mut $cb {
+                $cb_copy(q)
+            }
+            unsafe fn cbuffer_destroy(q: *mut $cb) -> c_int {
+                $cb_destroy(q)
+            }
+            unsafe fn cbuffer_reset(q: *mut $cb) -> c_int {
+                $cb_reset(q)
+            }
+            unsafe fn cbuffer_size(q: *mut $cb) -> c_uint {
+                $cb_size(q)
+            }
+            unsafe fn cbuffer_max_size(q: *mut $cb) -> c_uint {
+                $cb_max_size(q)
+            }
+            unsafe fn cbuffer_max_read(q: *mut $cb) -> c_uint {
+                $cb_max_read(q)
+            }
+            unsafe fn cbuffer_space_available(q: *mut $cb) -> c_uint {
+                $cb_space(q)
+            }
+            unsafe fn cbuffer_push(q: *mut $cb, v: $t) -> c_int {
+                $cb_push(q, v)
+            }
+            unsafe fn cbuffer_write(q: *mut $cb, v: *mut $t, n: c_uint) -> c_int {
+                $cb_write(q, v, n)
+            }
+            unsafe fn cbuffer_pop(q: *mut $cb, v: *mut $t) -> c_int {
+                $cb_pop(q, v)
+            }
+            unsafe fn cbuffer_read(
+                q: *mut $cb,
+                n: c_uint,
+                v: *mut *mut $t,
+                r: *mut c_uint,
+            ) -> c_int {
+                $cb_read(q, n, v, r)
+            }
+            unsafe fn cbuffer_release(q: *mut $cb, n: c_uint) -> c_int {
+                $cb_release(q, n)
+            }
+
+            unsafe fn window_create(n: c_uint) -> *mut $win {
+                $win_create(n)
+            }
+            unsafe fn window_recreate(q: *mut $win, n: c_uint) -> *mut $win {
+                $win_recreate(q, n)
+            }
+            unsafe fn window_copy(q: *mut $win) -> *mut $win {
+                $win_copy(q)
+            }
+            unsafe fn window_destroy(q: *mut $win) -> c_int {
+                $win_destroy(q)
+            }
+            unsafe fn window_reset(q: *mut $win) -> c_int {
+                $win_reset(q)
+            }
+            unsafe fn window_read(q: *mut $win, v: *mut *mut $t) -> c_int {
+                $win_read(q, v)
+            }
+            unsafe fn window_index(q: *mut $win, i: c_uint, v: *mut $t) -> c_int {
+                $win_index(q, i, v)
+            }
+            unsafe fn window_push(q: *mut $win, v: $t) -> c_int {
+                $win_push(q, v)
+            }
+            unsafe fn window_write(q: *mut $win, v: *mut $t, n: c_uint) -> c_int {
+                $win_write(q, v, n)
+            }
+
+            unsafe fn wdelay_create(delay: c_uint) -> *mut $wd {
+                $wd_create(delay)
+            }
+            unsafe fn wdelay_recreate(q: *mut $wd, delay: c_uint) -> *mut $wd {
+                $wd_recreate(q, delay)
+            }
+            unsafe fn wdelay_copy(q: *mut $wd) -> *mut $wd {
+                $wd_copy(q)
+            }
+            unsafe fn wdelay_destroy(q: *mut $wd) -> c_int {
+                $wd_destroy(q)
+            }
+            unsafe fn wdelay_reset(q: *mut $wd) -> c_int {
+                $wd_reset(q)
+            }
+            unsafe fn wdelay_read(q: *mut $wd, v: *mut $t) -> c_int {
+                $wd_read(q, v)
+            }
+            unsafe fn wdelay_push(q: *mut $wd, v: $t) -> c_int {
+                $wd_push(q, v)
+            }
+        }
+    };
+}
+
+impl_buffer_sample! {
+    f32,
+    cbuffer: cbufferf_s {
+        cbufferf_create_max, cbufferf_copy, cbufferf_destroy, cbufferf_reset,
+        cbufferf_size, cbufferf_max_size, cbufferf_max_read, cbufferf_space_available,
+        cbufferf_push, cbufferf_write, cbufferf_pop, cbufferf_read, cbufferf_release,
+    },
+    window: windowf_s {
+        windowf_create, windowf_recreate, windowf_copy, windowf_destroy,
+        windowf_reset, windowf_read, windowf_index, windowf_push,
+        windowf_write,
+    },
+    wdelay: wdelayf_s {
+        wdelayf_create, wdelayf_recreate, wdelayf_copy, wdelayf_destroy,
+        wdelayf_reset, wdelayf_read, wdelayf_push,
+    },
+}
+
+impl_buffer_sample! {
+    Complex32,
+    cbuffer: cbuffercf_s {
+        cbuffercf_create_max, cbuffercf_copy, cbuffercf_destroy, cbuffercf_reset,
+        cbuffercf_size, cbuffercf_max_size, cbuffercf_max_read, cbuffercf_space_available,
+        cbuffercf_push, cbuffercf_write, cbuffercf_pop, cbuffercf_read, cbuffercf_release,
+    },
+    window: windowcf_s {
+        windowcf_create, windowcf_recreate, windowcf_copy, windowcf_destroy,
+        windowcf_reset, windowcf_read, windowcf_index, windowcf_push,
+        windowcf_write,
+    },
+    wdelay: wdelaycf_s {
+        wdelaycf_create, wdelaycf_recreate, wdelaycf_copy, wdelaycf_destroy,
+        wdelaycf_reset, wdelaycf_read, wdelaycf_push,
+    },
+}
+
+/// Fixed-capacity first-in first-out buffer (`cbufferf`/`cbuffercf`).
+pub struct CircularBuffer<T: BufferSample> {
+    q: *mut T::CBuffer,
+}
+
+unsafe impl<T: BufferSample> Send for CircularBuffer<T> {}
+
+impl<T: BufferSample> CircularBuffer<T> {
+    /// Creates a buffer holding up to `max_size` samples, any of which can be
+    /// read at once.
+    pub fn create(max_size: usize) -> Result<CircularBuffer<T>> {
+        CircularBuffer::create_max(max_size, max_size)
+    }
+
+    /// Creates a buffer holding up to `max_size` samples, of which at most
+    /// `max_read` can be read at once.
+    pub fn create_max(max_size: usize, max_read: usize) -> Result<CircularBuffer<T>> {
+        let q = created(unsafe { T::cbuffer_create_max(max_size as _, max_read as _) })?;
+        Ok(CircularBuffer { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::cbuffer_reset(self.q) });
+        Ok(())
+    }
+
+    /// Number of samples currently held.
+    pub fn len(&self) -> usize {
+        unsafe { T::cbuffer_size(self.q) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.space_available() == 0
+    }
+
+    pub fn max_size(&self) -> usize {
+        unsafe { T::cbuffer_max_size(self.q) as usize }
+    }
+
+    /// Largest number of samples a single [`read`](Self::read) can return.
+    pub fn max_read(&self) -> usize {
+        unsafe { T::cbuffer_max_read(self.q) as usize }
+    }
+
+    pub fn space_available(&self) -> usize {
+        unsafe { T::cbuffer_space_available(self.q) as usize }
+    }
+
+    /// Appends one sample; fails with [`Error::EIRANGE`] when the buffer is full.
+    pub fn push(&mut self, v: T) -> Result<()> {
+        if self.is_full() {
+            return Err(Error::EIRANGE);
+        }
+        liquid_try!(unsafe { T::cbuffer_push(self.q, v) });
+        Ok(())
+    }
+
+    /// Appends `v`; fails with [`Error::EIRANGE`], writing nothing, when there
+    /// is not room for all of it.
+    pub fn write(&mut self, v: &[T]) -> Result<()> {
+        if v.len() > self.space_available() {
+            return Err(Error::EIRANGE);
+        }
+        liquid_try!(unsafe { T::cbuffer_write(self.q, v.as_ptr() as *mut _, v.len() as _) });
+        Ok(())
+    }
+
+    /// Removes and returns the oldest sample.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut v = T::default();
+        unsafe { T::cbuffer_pop(self.q, &mut v) };
+        Some(v)
+    }
+
+    /// Borrows up to `n` of the oldest samples without removing them; the
+    /// slice may be shorter than `n`. Follow with [`release`](Self::release)
+    /// to consume them.
+    pub fn read(&mut self, n: usize) -> Result<&[T]> {
+        let mut v = ptr::null_mut();
+        let mut num_read = 0;
+        liquid_try!(unsafe { T::cbuffer_read(self.q, n as _, &mut v, &mut num_read) });
+        if v.is_null() || num_read == 0 {
+            return Ok(&[]);
+        }
+        Ok(unsafe { slice::from_raw_parts(v, num_read as usize) })
+    }
+
+    /// Removes the `n` oldest samples; fails with [`Error::EIRANGE`] if fewer
+    /// than `n` are held.
+    pub fn release(&mut self, n: usize) -> Result<()> {
+        if n > self.len() {
+            return Err(Error::EIRANGE);
+        }
+        liquid_try!(unsafe { T::cbuffer_release(self.q, n as _) });
+        Ok(())
+    }
+}
+
+impl<T: BufferSample> Clone for CircularBuffer<T> {
+    fn clone(&self) -> Self {
+        CircularBuffer {
+            q: unsafe { T::cbuffer_copy(self.q) },
+        }
+    }
+}
+
+impl<T: BufferSample> Drop for CircularBuffer<T> {
+    fn drop(&mut self) {
+        unsafe { T::cbuffer_destroy(self.q) };
+    }
+}
+
+impl<T: BufferSample> IntoIterator for CircularBuffer<T> {
+    type Item = T;
+    type IntoIter = CircularBufferIter<T>;
+
+    /// Drains the buffer, oldest sample first.
+    fn into_iter(self) -> CircularBufferIter<T> {
+        CircularBufferIter { buffer: self }
+    }
+}
+
+/// Owning iterator over a [`CircularBuffer`], oldest sample first.
+pub struct CircularBufferIter<T: BufferSample> {
+    buffer: CircularBuffer<T>,
+}
+
+impl<T: BufferSample> Iterator for CircularBufferIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buffer.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.buffer.len();
+        (len, Some(len))
+    }
+}
+
+impl<T: BufferSample> ExactSizeIterator for CircularBufferIter<T> {}
+
+/// Window over the most recent `len` samples; pushing a sample drops the
+/// oldest (`windowf`/`windowcf`).
+pub struct SlidingWindow<T: BufferSample> {
+    q: *mut T::Window,
+    len: usize,
+}
+
+unsafe impl<T: BufferSample> Send for SlidingWindow<T> {}
+
+impl<T: BufferSample> SlidingWindow<T> {
+    /// Creates a window of `len` samples, initially zero; fails with
+    /// [`Error::EIRANGE`] if `len` is zero.
+    pub fn create(len: usize) -> Result<SlidingWindow<T>> {
+        if len == 0 {
+            return Err(Error::EIRANGE);
+        }
+        let q = created(unsafe { T::window_create(len as _) })?;
+        Ok(SlidingWindow { q, len })
+    }
+
+    /// Resizes the window, keeping the most recent samples; fails with
+    /// [`Error::EIRANGE`] if `len` is zero, leaving the window unchanged.
+    pub fn recreate(&mut self, len: usize) -> Result<()> {
+        if len == 0 {
+            return Err(Error::EIRANGE);
+        }
+        self.q = created(unsafe { T::window_recreate(self.q, len as _) })?;
+        self.len = len;
+        Ok(())
+    }
+
+    /// Zeroes the window.
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::window_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, v: T) -> Result<()> {
+        liquid_try!(unsafe { T::window_push(self.q, v) });
+        Ok(())
+    }
+
+    pub fn write(&mut self, v: &[T]) -> Result<()> {
+        liquid_try!(unsafe { T::window_write(self.q, v.as_ptr() as *mut _, v.len() as _) });
+        Ok(())
+    }
+
+    /// Sample `i`, counting from the oldest; fails with [`Error::EIRANGE`]
+    /// when `i` is out of bounds.
+    pub fn get(&self, i: usize) -> Result<T> {
+        if i >= self.len {
+            return Err(Error::EIRANGE);
+        }
+        let mut v = T::default();
+        liquid_try!(unsafe { T::window_index(self.q, i as _, &mut v) });
+        Ok(v)
+    }
+
+    /// Borrows the window contents, oldest sample first.
+    pub fn read(&mut self) -> Result<&[T]> {
+        let mut v = ptr::null_mut();
+        liquid_try!(unsafe { T::window_read(self.q, &mut v) });
+        if v.is_null() || self.len == 0 {
+            return Ok(&[]);
+        }
+        Ok(unsafe { slice::from_raw_parts(v, self.len) })
+    }
+}
+
+impl<T: BufferSample> Clone for SlidingWindow<T> {
+    fn clone(&self) -> Self {
+        SlidingWindow {
+            q: unsafe { T::window_copy(self.q) },
+            len: self.len,
+        }
+    }
+}
+
+impl<T: BufferSample> Drop for SlidingWindow<T> {
+    fn drop(&mut self) {
+        unsafe { T::window_destroy(self.q) };
+    }
+}
+
+impl<T: BufferSample> Extend<T> for SlidingWindow<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            unsafe { T::window_push(self.q, v) };
+        }
+    }
+}
+
+impl<'a, T: BufferSample> IntoIterator for &'a mut SlidingWindow<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.read().unwrap_or(&[]).iter()
+    }
+}
+
+/// Fixed integer delay: each pushed sample comes out `delay` pushes later
+/// (`wdelayf`/`wdelaycf`).
+pub struct DelayLine<T: BufferSample> {
+    q: *mut T::WDelay,
+    delay: usize,
+}
+
+unsafe impl<T: BufferSample> Send for DelayLine<T> {}
+
+impl<T: BufferSample> DelayLine<T> {
+    pub fn create(delay: usize) -> Result<DelayLine<T>> {
+        let q = created(unsafe { T::wdelay_create(delay as _) })?;
+        Ok(DelayLine { q, delay })
+    }
+
+    /// Changes the delay, keeping as many of the buffered samples as fit.
+    pub fn recreate(&mut self, delay: usize) -> Result<()> {
+        self.q = created(unsafe { T::wdelay_recreate(self.q, delay as _) })?;
+        self.delay = delay;
+        Ok(())
+    }
+
+    /// Zeroes the delay line.
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::wdelay_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn delay(&self) -> usize {
+        self.delay
+    }
+
+    pub fn push(&mut self, v: T) -> Result<()> {
+        liquid_try!(unsafe { T::wdelay_push(self.q, v) });
+        Ok(())
+    }
+
+    /// The sample pushed `delay` pushes ago.
+    pub fn read(&self) -> T {
+        let mut v = T::default();
+        unsafe { T::wdelay_read(self.q, &mut v) };
+        v
+    }
+
+    /// Pushes `v` and returns the sample it displaces.
+    pub fn execute(&mut self, v: T) -> T {
+        unsafe { T::wdelay_push(self.q, v) };
+        self.read()
+    }
+}
+
+impl<T: BufferSample> Clone for DelayLine<T> {
+    fn clone(&self) -> Self {
+        DelayLine {
+            q: unsafe { T::wdelay_copy(self.q) },
+            delay: self.delay,
+        }
+    }
+}
+
+impl<T: BufferSample> Drop for DelayLine<T> {
+    fn drop(&mut self) {
+        unsafe { T::wdelay_destroy(self.q) };
+    }
+}
+
+impl<T: BufferSample> Extend<T> for DelayLine<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for v in iter {
+            unsafe { T::wdelay_push(self.q, v) };
+        }
+    }
+}
@@ -0,0 +1,502 @@
+//! Continuous-phase modulators: `cpfskmod`/`cpfskdem`, `gmskmod`/`gmskdem` and `fskmod`/`fskdem`.
+
+use liquidizers_sys::*;
+
+use std::os::raw::{c_int, c_uint};
+
+use crate::{check_len, created, Complex32, Result};
+
+/// Pulse shape used by the CPFSK modulator and demodulator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum CpfskFilter {
+    Square,
+    RcosFull,
+    RcosPartial,
+    Gmsk,
+}
+
+impl CpfskFilter {
+    pub fn from_raw(raw: c_int) -> Option<CpfskFilter> {
+        match raw as liquid_cpfsk_filter {
+            liquid_cpfsk_filter_LIQUID_CPFSK_SQUARE => Some(CpfskFilter::Square),
+            liquid_cpfsk_filter_LIQUID_CPFSK_RCOS_FULL => Some(CpfskFilter::RcosFull),
+            liquid_cpfsk_filter_LIQUID_CPFSK_RCOS_PARTIAL => Some(CpfskFilter::RcosPartial),
+            liquid_cpfsk_filter_LIQUID_CPFSK_GMSK => Some(CpfskFilter::Gmsk),
+            _ => None,
+        }
+    }
+
+    pub fn to_raw(self) -> c_int {
+        (match self {
+            CpfskFilter::Square => liquid_cpfsk_filter_LIQUID_CPFSK_SQUARE,
+            CpfskFilter::RcosFull => liquid_cpfsk_filter_LIQUID_CPFSK_RCOS_FULL,
+            CpfskFilter::RcosPartial => liquid_cpfsk_filter_LIQUID_CPFSK_RCOS_PARTIAL,
+            CpfskFilter::Gmsk => liquid_cpfsk_filter_LIQUID_CPFSK_GMSK,
+        }) as c_int
+    }
+}
+
+/// A modulator that maps one symbol onto a fixed number of complex samples.
+pub trait SymbolModulator {
+    /// Number of bits carried by each symbol.
+    fn bits_per_symbol(&self) -> u32;
+
+    /// Number of samples produced for each symbol.
+    fn samples_per_symbol(&self) -> usize;
+
+    /// Modulates `sym` into `out`, which must hold exactly `samples_per_symbol()` samples.
+    fn modulate_into(&mut self, sym: u32, out: &mut [Complex32]) -> Result<()>;
+
+    /// Modulates `sym`, returning `samples_per_symbol()` samples.
+    fn modulate(&mut self, sym: u32) -> Result<Vec<Complex32>> {
+        let mut out = vec![Complex32::default(); self.samples_per_symbol()];
+        self.modulate_into(sym, &mut out)?;
+        Ok(out)
+    }
+
+    /// Streams `bits` through the modulator, packing `bits_per_symbol()` bits
+    /// (most significant first) into each symbol. A trailing partial symbol is
+    /// padded with zeros.
+    fn modulate_bits<I>(&mut self, bits: I) -> ModulatedSamples<'_, Self, I::IntoIter>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = bool>,
+    {
+        ModulatedSamples {
+            modulator: self,
+            bits: bits.into_iter(),
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+/// Iterator over the samples produced by [`SymbolModulator::modulate_bits`].
+pub struct ModulatedSamples<'a, M: SymbolModulator, I: Iterator<Item = bool>> {
+    modulator: &'a mut M,
+    bits: I,
+    buf: Vec<Complex32>,
+    pos: usize,
+}
+
+impl<'a, M: SymbolModulator, I: Iterator<Item = bool>> Iterator for ModulatedSamples<'a, M, I> {
+    type Item = Result<Complex32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos == self.buf.len() {
+            let bps = self.modulator.bits_per_symbol();
+            let mut sym = 0;
+            let mut taken = 0;
+            while taken < bps {
+                match self.bits.next() {
+                    Some(bit) => sym = (sym << 1) | bit as u32,
+                    None if taken == 0 => return None,
+                    None => sym <<= 1,
+                }
+                taken += 1;
+            }
+            self.buf
+                .resize(self.modulator.samples_per_symbol(), Complex32::default());
+            if let Err(e) = self.modulator.modulate_into(sym, &mut self.buf) {
+                return Some(Err(e));
+            }
+            self.pos = 0;
+        }
+        let sample = self.buf[self.pos];
+        self.pos += 1;
+        Some(Ok(sample))
+    }
+}
+
+/// Continuous-phase frequency-shift keying modulator (`cpfskmod`).
+pub struct CpfskMod {
+    q: cpfskmod,
+}
+
+unsafe impl Send for CpfskMod {}
+
+impl CpfskMod {
+    /// Creates a modulator with `bps` bits/symbol, modulation index `h`, `k`
+    /// samples/symbol, filter delay `m` symbols and filter roll-off `beta`.
+    pub fn create(
+        bps: u32,
+        h: f32,
+        k: u32,
+        m: u32,
+        beta: f32,
+        filter: CpfskFilter,
+    ) -> Result<CpfskMod> {
+        let q = created(unsafe { cpfskmod_create(bps, h, k, m, beta, filter.to_raw()) })?;
+        Ok(CpfskMod { q })
+    }
+
+    /// Creates a minimum-shift keying modulator with `k` samples/symbol.
+    pub fn create_msk(k: u32) -> Result<CpfskMod> {
+        let q = created(unsafe { cpfskmod_create_msk(k) })?;
+        Ok(CpfskMod { q })
+    }
+
+    /// Creates a Gaussian minimum-shift keying modulator.
+    pub fn create_gmsk(k: u32, m: u32, bt: f32) -> Result<CpfskMod> {
+        let q = created(unsafe { cpfskmod_create_gmsk(k, m, bt) })?;
+        Ok(CpfskMod { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { cpfskmod_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn modulation_index(&self) -> f32 {
+        unsafe { cpfskmod_get_modulation_index(self.q) }
+    }
+
+    pub fn delay(&self) -> u32 {
+        unsafe { cpfskmod_get_delay(self.q) }
+    }
+
+    pub fn beta(&self) -> f32 {
+        unsafe { cpfskmod_get_beta(self.q) }
+    }
+
+    pub fn filter(&self) -> Option<CpfskFilter> {
+        CpfskFilter::from_raw(unsafe { cpfskmod_get_type(self.q) })
+    }
+}
+
+impl SymbolModulator for CpfskMod {
+    fn bits_per_symbol(&self) -> u32 {
+        unsafe { cpfskmod_get_bits_per_symbol(self.q) }
+    }
+
+    fn samples_per_symbol(&self) -> usize {
+        unsafe { cpfskmod_get_samples_per_symbol(self.q) as usize }
+    }
+
+    fn modulate_into(&mut self, sym: u32, out: &mut [Complex32]) -> Result<()> {
+        check_len(out.len(), self.samples_per_symbol())?;
+        liquid_try!(unsafe { cpfskmod_modulate(self.q, sym, out.as_mut_ptr()) });
+        Ok(())
+    }
+}
+
+impl Clone for CpfskMod {
+    fn clone(&self) -> Self {
+        CpfskMod {
+            q: unsafe { cpfskmod_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for CpfskMod {
+    fn drop(&mut self) {
+        unsafe { cpfskmod_destroy(self.q) };
+    }
+}
+
+/// Continuous-phase frequency-shift keying demodulator (`cpfskdem`).
+pub struct CpfskDem {
+    q: cpfskdem,
+}
+
+unsafe impl Send for CpfskDem {}
+
+impl CpfskDem {
+    /// Creates a demodulator matching [`CpfskMod::create`].
+    pub fn create(
+        bps: u32,
+        h: f32,
+        k: u32,
+        m: u32,
+        beta: f32,
+        filter: CpfskFilter,
+    ) -> Result<CpfskDem> {
+        let q = created(unsafe { cpfskdem_create(bps, h, k, m, beta, filter.to_raw()) })?;
+        Ok(CpfskDem { q })
+    }
+
+    /// Creates a minimum-shift keying demodulator with `k` samples/symbol.
+    pub fn create_msk(k: u32) -> Result<CpfskDem> {
+        let q = created(unsafe { cpfskdem_create_msk(k) })?;
+        Ok(CpfskDem { q })
+    }
+
+    /// Creates a Gaussian minimum-shift keying demodulator.
+    pub fn create_gmsk(k: u32, m: u32, bt: f32) -> Result<CpfskDem> {
+        let q = created(unsafe { cpfskdem_create_gmsk(k, m, bt) })?;
+        Ok(CpfskDem { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { cpfskdem_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn bits_per_symbol(&self) -> u32 {
+        unsafe { cpfskdem_get_bits_per_symbol(self.q) }
+    }
+
+    pub fn samples_per_symbol(&self) -> usize {
+        unsafe { cpfskdem_get_samples_per_symbol(self.q) as usize }
+    }
+
+    pub fn modulation_index(&self) -> f32 {
+        unsafe { cpfskdem_get_modulation_index(self.q) }
+    }
+
+    pub fn delay(&self) -> u32 {
+        unsafe { cpfskdem_get_delay(self.q) }
+    }
+
+    pub fn beta(&self) -> f32 {
+        unsafe { cpfskdem_get_beta(self.q) }
+    }
+
+    pub fn filter(&self) -> Option<CpfskFilter> {
+        CpfskFilter::from_raw(unsafe { cpfskdem_get_type(self.q) })
+    }
+
+    /// Demodulates one symbol from exactly `samples_per_symbol()` samples.
+    pub fn demodulate(&mut self, y: &[Complex32]) -> Result<u32> {
+        check_len(y.len(), self.samples_per_symbol())?;
+        Ok(unsafe { cpfskdem_demodulate(self.q, y.as_ptr() as *mut _) })
+    }
+}
+
+impl Clone for CpfskDem {
+    fn clone(&self) -> Self {
+        CpfskDem {
+            q: unsafe { cpfskdem_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for CpfskDem {
+    fn drop(&mut self) {
+        unsafe { cpfskdem_destroy(self.q) };
+    }
+}
+
+/// Gaussian minimum-shift keying modulator (`gmskmod`).
+pub struct GmskMod {
+    q: gmskmod,
+    k: c_uint,
+}
+
+unsafe impl Send for GmskMod {}
+
+impl GmskMod {
+    /// Creates a modulator with `k` samples/symbol, filter delay `m` symbols and
+    /// bandwidth-time product `bt`.
+    pub fn create(k: u32, m: u32, bt: f32) -> Result<GmskMod> {
+        let q = created(unsafe { gmskmod_create(k, m, bt) })?;
+        Ok(GmskMod { q, k })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { gmskmod_reset(self.q) });
+        Ok(())
+    }
+}
+
+impl SymbolModulator for GmskMod {
+    fn bits_per_symbol(&self) -> u32 {
+        1
+    }
+
+    fn samples_per_symbol(&self) -> usize {
+        self.k as usize
+    }
+
+    fn modulate_into(&mut self, sym: u32, out: &mut [Complex32]) -> Result<()> {
+        check_len(out.len(), self.samples_per_symbol())?;
+        liquid_try!(unsafe { gmskmod_modulate(self.q, sym, out.as_mut_ptr()) });
+        Ok(())
+    }
+}
+
+impl Clone for GmskMod {
+    fn clone(&self) -> Self {
+        GmskMod {
+            q: unsafe { gmskmod_copy(self.q) },
+            k: self.k,
+        }
+    }
+}
+
+impl Drop for GmskMod {
+    fn drop(&mut self) {
+        unsafe { gmskmod_destroy(self.q) };
+    }
+}
+
+/// Gaussian minimum-shift keying demodulator (`gmskdem`).
+pub struct GmskDem {
+    q: gmskdem,
+    k: c_uint,
+}
+
+unsafe impl Send for GmskDem {}
+
+impl GmskDem {
+    /// Creates a demodulator matching [`GmskMod::create`].
+    pub fn create(k: u32, m: u32, bt: f32) -> Result<GmskDem> {
+        let q = created(unsafe { gmskdem_create(k, m, bt) })?;
+        Ok(GmskDem { q, k })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { gmskdem_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn samples_per_symbol(&self) -> usize {
+        self.k as usize
+    }
+
+    /// Sets the bandwidth of the internal equalizer.
+    pub fn set_eq_bw(&mut self, bw: f32) -> Result<()> {
+        liquid_try!(unsafe { gmskdem_set_eq_bw(self.q, bw) });
+        Ok(())
+    }
+
+    /// Demodulates one symbol from exactly `samples_per_symbol()` samples.
+    pub fn demodulate(&mut self, y: &[Complex32]) -> Result<u32> {
+        check_len(y.len(), self.samples_per_symbol())?;
+        let mut sym = 0;
+        liquid_try!(unsafe { gmskdem_demodulate(self.q, y.as_ptr() as *mut _, &mut sym) });
+        Ok(sym)
+    }
+}
+
+impl Clone for GmskDem {
+    fn clone(&self) -> Self {
+        GmskDem {
+            q: unsafe { gmskdem_copy(self.q) },
+            k: self.k,
+        }
+    }
+}
+
+impl Drop for GmskDem {
+    fn drop(&mut self) {
+        unsafe { gmskdem_destroy(self.q) };
+    }
+}
+
+/// M-ary frequency-shift keying modulator (`fskmod`).
+pub struct FskMod {
+    q: fskmod,
+    m: c_uint,
+    k: c_uint,
+}
+
+unsafe impl Send for FskMod {}
+
+impl FskMod {
+    /// Creates a modulator with `m` bits/symbol, `k` samples/symbol and total
+    /// signal `bandwidth` in (0, 0.5).
+    pub fn create(m: u32, k: u32, bandwidth: f32) -> Result<FskMod> {
+        let q = created(unsafe { fskmod_create(m, k, bandwidth) })?;
+        Ok(FskMod { q, m, k })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { fskmod_reset(self.q) });
+        Ok(())
+    }
+}
+
+impl SymbolModulator for FskMod {
+    fn bits_per_symbol(&self) -> u32 {
+        self.m
+    }
+
+    fn samples_per_symbol(&self) -> usize {
+        self.k as usize
+    }
+
+    fn modulate_into(&mut self, sym: u32, out: &mut [Complex32]) -> Result<()> {
+        check_len(out.len(), self.samples_per_symbol())?;
+        liquid_try!(unsafe { fskmod_modulate(self.q, sym, out.as_mut_ptr()) });
+        Ok(())
+    }
+}
+
+impl Clone for FskMod {
+    fn clone(&self) -> Self {
+        FskMod {
+            q: unsafe { fskmod_copy(self.q) },
+            m: self.m,
+            k: self.k,
+        }
+    }
+}
+
+impl Drop for FskMod {
+    fn drop(&mut self) {
+        unsafe { fskmod_destroy(self.q) };
+    }
+}
+
+/// M-ary frequency-shift keying demodulator (`fskdem`).
+pub struct FskDem {
+    q: fskdem,
+    m: c_uint,
+    k: c_uint,
+}
+
+unsafe impl Send for FskDem {}
+
+impl FskDem {
+    /// Creates a demodulator matching [`FskMod::create`].
+    pub fn create(m: u32, k: u32, bandwidth: f32) -> Result<FskDem> {
+        let q = created(unsafe { fskdem_create(m, k, bandwidth) })?;
+        Ok(FskDem { q, m, k })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { fskdem_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn bits_per_symbol(&self) -> u32 {
+        self.m
+    }
+
+    pub fn samples_per_symbol(&self) -> usize {
+        self.k as usize
+    }
+
+    /// Demodulates one symbol from exactly `samples_per_symbol()` samples.
+    pub fn demodulate(&mut self, y: &[Complex32]) -> Result<u32> {
+        check_len(y.len(), self.samples_per_symbol())?;
+        Ok(unsafe { fskdem_demodulate(self.q, y.as_ptr() as *mut _) })
+    }
+
+    /// Frequency error estimate of the last demodulated symbol.
+    pub fn frequency_error(&self) -> f32 {
+        unsafe { fskdem_get_frequency_error(self.q) }
+    }
+
+    /// Energy of symbol `s` over `range` neighbouring FFT bins.
+    pub fn symbol_energy(&self, s: u32, range: u32) -> f32 {
+        unsafe { fskdem_get_symbol_energy(self.q, s, range) }
+    }
+}
+
+impl Clone for FskDem {
+    fn clone(&self) -> Self {
+        FskDem {
+            q: unsafe { fskdem_copy(self.q) },
+            m: self.m,
+            k: self.k,
+        }
+    }
+}
+
+impl Drop for FskDem {
+    fn drop(&mut self) {
+        unsafe { fskdem_destroy(self.q) };
+    }
+}
@@ -0,0 +1,356 @@
+//! Direct-sequence spread-spectrum frames: `dsssframegen`/`dsssframesync` and
+//! the fixed-size `dsssframe64gen`/`dsssframe64sync`.
+//!
+//! The spreading factor is not exposed: liquid fixes it inside the
+//! generators and synchronizers and provides neither a getter nor a public
+//! constant for it, so there is nothing to report that would stay in step
+//! with the linked library. [`DsssFrameGen::frame_len`] and
+//! [`DsssFrame64Gen::frame_len`] give the resulting on-air length.
+
+use liquidizers_sys::*;
+
+use std::ptr;
+
+use crate::framing::{framesync_trampoline, FrameCallback, DEFAULT_HEADER_LEN};
+use crate::{created, Complex32, CrcScheme, Error, FecScheme, Frame, FrameDataStats, Result};
+
+/// Payload protection used by [`DsssFrameGen`] (`dsssframegenprops_s`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct DsssFrameGenProps {
+    pub check: CrcScheme,
+    pub fec0: FecScheme,
+    pub fec1: FecScheme,
+}
+
+impl DsssFrameGenProps {
+    fn to_raw(self) -> dsssframegenprops_s {
+        dsssframegenprops_s {
+            check: self.check.to_raw(),
+            fec0: self.fec0.to_raw(),
+            fec1: self.fec1.to_raw(),
+        }
+    }
+
+    fn from_raw(props: dsssframegenprops_s) -> DsssFrameGenProps {
+        DsssFrameGenProps {
+            check: CrcScheme::from_raw(props.check).unwrap_or(CrcScheme::None),
+            fec0: FecScheme::from_raw(props.fec0).unwrap_or(FecScheme::None),
+            fec1: FecScheme::from_raw(props.fec1).unwrap_or(FecScheme::None),
+        }
+    }
+}
+
+fn check_header(header: &[u8], header_len: usize) -> Result<()> {
+    if header.len() == header_len {
+        Ok(())
+    } else {
+        Err(Error::EIRANGE)
+    }
+}
+
+/// Variable-length spread-spectrum frame generator (`dsssframegen`).
+pub struct DsssFrameGen {
+    q: dsssframegen,
+    header_len: usize,
+}
+
+unsafe impl Send for DsssFrameGen {}
+
+impl DsssFrameGen {
+    /// Creates a generator with liquid's default payload properties.
+    pub fn create() -> Result<DsssFrameGen> {
+        let q = created(unsafe { dsssframegen_create(ptr::null_mut()) })?;
+        Ok(DsssFrameGen {
+            q,
+            header_len: DEFAULT_HEADER_LEN,
+        })
+    }
+
+    pub fn create_with_props(props: DsssFrameGenProps) -> Result<DsssFrameGen> {
+        let mut props = props.to_raw();
+        let q = created(unsafe { dsssframegen_create(&mut props) })?;
+        Ok(DsssFrameGen {
+            q,
+            header_len: DEFAULT_HEADER_LEN,
+        })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { dsssframegen_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn is_assembled(&self) -> bool {
+        unsafe { dsssframegen_is_assembled(self.q) != 0 }
+    }
+
+    pub fn props(&self) -> DsssFrameGenProps {
+        let mut props = dsssframegenprops_s::default();
+        unsafe { dsssframegen_getprops(self.q, &mut props) };
+        DsssFrameGenProps::from_raw(props)
+    }
+
+    pub fn set_props(&mut self, props: DsssFrameGenProps) -> Result<()> {
+        let mut props = props.to_raw();
+        liquid_try!(unsafe { dsssframegen_setprops(self.q, &mut props) });
+        Ok(())
+    }
+
+    pub fn header_len(&self) -> usize {
+        self.header_len
+    }
+
+    pub fn set_header_len(&mut self, len: usize) -> Result<()> {
+        liquid_try!(unsafe { dsssframegen_set_header_len(self.q, len as _) });
+        self.header_len = len;
+        Ok(())
+    }
+
+    pub fn set_header_props(&mut self, props: DsssFrameGenProps) -> Result<()> {
+        let mut props = props.to_raw();
+        liquid_try!(unsafe { dsssframegen_set_header_props(self.q, &mut props) });
+        Ok(())
+    }
+
+    /// Number of samples in the assembled frame.
+    pub fn frame_len(&self) -> usize {
+        unsafe { dsssframegen_getframelen(self.q) as usize }
+    }
+
+    /// Assembles a frame; `header` must be exactly `header_len()` bytes.
+    pub fn assemble(&mut self, header: &[u8], payload: &[u8]) -> Result<()> {
+        check_header(header, self.header_len)?;
+        liquid_try!(unsafe {
+            dsssframegen_assemble(
+                self.q,
+                header.as_ptr(),
+                payload.as_ptr(),
+                payload.len() as _,
+            )
+        });
+        Ok(())
+    }
+
+    /// Writes the next `buf.len()` samples of the assembled frame, returning
+    /// `true` once the frame is complete.
+    pub fn write_samples(&mut self, buf: &mut [Complex32]) -> Result<bool> {
+        if !self.is_assembled() {
+            return Err(Error::ENOINIT);
+        }
+        let rc = unsafe { dsssframegen_write_samples(self.q, buf.as_mut_ptr(), buf.len() as _) };
+        Ok(rc != 0)
+    }
+}
+
+impl Drop for DsssFrameGen {
+    fn drop(&mut self) {
+        unsafe { dsssframegen_destroy(self.q) };
+    }
+}
+
+/// Synchronizer for [`DsssFrameGen`] frames (`dsssframesync`).
+pub struct DsssFrameSync {
+    q: dsssframesync,
+    callback: Box<FrameCallback>,
+}
+
+impl DsssFrameSync {
+    /// Creates a synchronizer that calls `callback` for every detected frame.
+    pub fn create<F>(callback: F) -> Result<DsssFrameSync>
+    where
+        F: FnMut(Frame<'_>) + 'static,
+    {
+        let mut callback = FrameCallback::new(DEFAULT_HEADER_LEN, callback);
+        let q = created(unsafe {
+            dsssframesync_create(Some(framesync_trampoline), callback.userdata())
+        })?;
+        Ok(DsssFrameSync { q, callback })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { dsssframesync_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn is_frame_open(&self) -> bool {
+        unsafe { dsssframesync_is_frame_open(self.q) != 0 }
+    }
+
+    pub fn set_header_len(&mut self, len: usize) -> Result<()> {
+        liquid_try!(unsafe { dsssframesync_set_header_len(self.q, len as _) });
+        self.callback.header_len = len;
+        Ok(())
+    }
+
+    pub fn set_header_props(&mut self, props: DsssFrameGenProps) -> Result<()> {
+        let mut props = props.to_raw();
+        liquid_try!(unsafe { dsssframesync_set_header_props(self.q, &mut props) });
+        Ok(())
+    }
+
+    pub fn decode_header_soft(&mut self, soft: bool) -> Result<()> {
+        liquid_try!(unsafe { dsssframesync_decode_header_soft(self.q, soft as _) });
+        Ok(())
+    }
+
+    pub fn decode_payload_soft(&mut self, soft: bool) -> Result<()> {
+        liquid_try!(unsafe { dsssframesync_decode_payload_soft(self.q, soft as _) });
+        Ok(())
+    }
+
+    /// Pushes received samples through the synchronizer.
+    pub fn execute(&mut self, x: &[Complex32]) -> Result<()> {
+        liquid_try!(unsafe { dsssframesync_execute(self.q, x.as_ptr() as *mut _, x.len() as _) });
+        Ok(())
+    }
+
+    pub fn framedatastats(&self) -> FrameDataStats {
+        unsafe { dsssframesync_get_framedatastats(self.q) }.into()
+    }
+
+    pub fn reset_framedatastats(&mut self) -> Result<()> {
+        liquid_try!(unsafe { dsssframesync_reset_framedatastats(self.q) });
+        Ok(())
+    }
+}
+
+impl Drop for DsssFrameSync {
+    fn drop(&mut self) {
+        unsafe { dsssframesync_destroy(self.q) };
+    }
+}
+
+/// Fixed-size spread-spectrum frame generator carrying an 8-byte header and a
+/// 64-byte payload (`dsssframe64gen`).
+pub struct DsssFrame64Gen {
+    q: dsssframe64gen,
+}
+
+unsafe impl Send for DsssFrame64Gen {}
+
+impl DsssFrame64Gen {
+    pub fn create() -> Result<DsssFrame64Gen> {
+        let q = created(unsafe { dsssframe64gen_create() })?;
+        Ok(DsssFrame64Gen { q })
+    }
+
+    /// Number of samples in each frame.
+    pub fn frame_len(&self) -> usize {
+        unsafe { dsssframe64gen_get_frame_len(self.q) as usize }
+    }
+
+    /// Generates a complete frame into `buf`, which must hold `frame_len()` samples.
+    pub fn execute_into(
+        &mut self,
+        header: &[u8; 8],
+        payload: &[u8; 64],
+        buf: &mut [Complex32],
+    ) -> Result<()> {
+        if buf.len() != self.frame_len() {
+            return Err(Error::EIRANGE);
+        }
+        liquid_try!(unsafe {
+            dsssframe64gen_execute(self.q, header.as_ptr(), payload.as_ptr(), buf.as_mut_ptr())
+        });
+        Ok(())
+    }
+
+    /// Generates a complete frame.
+    pub fn execute(&mut self, header: &[u8; 8], payload: &[u8; 64]) -> Result<Vec<Complex32>> {
+        let mut buf = vec![Complex32::default(); self.frame_len()];
+        self.execute_into(header, payload, &mut buf)?;
+        Ok(buf)
+    }
+}
+
+impl Clone for DsssFrame64Gen {
+    fn clone(&self) -> Self {
+        DsssFrame64Gen {
+            q: unsafe { dsssframe64gen_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for DsssFrame64Gen {
+    fn drop(&mut self) {
+        unsafe { dsssframe64gen_destroy(self.q) };
+    }
+}
+
+/// Synchronizer for [`DsssFrame64Gen`] frames (`dsssframe64sync`).
+pub struct DsssFrame64Sync {
+    q: dsssframe64sync,
+    callback: Box<FrameCallback>,
+}
+
+impl DsssFrame64Sync {
+    /// Creates a synchronizer that calls `callback` for every detected frame.
+    pub fn create<F>(callback: F) -> Result<DsssFrame64Sync>
+    where
+        F: FnMut(Frame<'_>) + 'static,
+    {
+        let mut callback = FrameCallback::new(8, callback);
+        let q = created(unsafe {
+            dsssframe64sync_create(Some(framesync_trampoline), callback.userdata())
+        })?;
+        Ok(DsssFrame64Sync { q, callback })
+    }
+
+    /// Replaces the frame callback.
+    pub fn set_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(Frame<'_>) + 'static,
+    {
+        self.callback.callback = Box::new(callback);
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { dsssframe64sync_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn is_frame_open(&self) -> bool {
+        unsafe { dsssframe64sync_is_frame_open(self.q) != 0 }
+    }
+
+    /// Detection threshold of the preamble correlator.
+    pub fn threshold(&self) -> f32 {
+        unsafe { dsssframe64sync_get_threshold(self.q) }
+    }
+
+    pub fn set_threshold(&mut self, threshold: f32) -> Result<()> {
+        liquid_try!(unsafe { dsssframe64sync_set_threshold(self.q, threshold) });
+        Ok(())
+    }
+
+    /// Carrier offset search range, in radians/sample.
+    pub fn range(&self) -> f32 {
+        unsafe { dsssframe64sync_get_range(self.q) }
+    }
+
+    pub fn set_range(&mut self, dphi_max: f32) -> Result<()> {
+        liquid_try!(unsafe { dsssframe64sync_set_range(self.q, dphi_max) });
+        Ok(())
+    }
+
+    /// Pushes received samples through the synchronizer.
+    pub fn execute(&mut self, x: &[Complex32]) -> Result<()> {
+        liquid_try!(unsafe { dsssframe64sync_execute(self.q, x.as_ptr() as *mut _, x.len() as _) });
+        Ok(())
+    }
+
+    pub fn framedatastats(&self) -> FrameDataStats {
+        unsafe { dsssframe64sync_get_framedatastats(self.q) }.into()
+    }
+
+    pub fn reset_framedatastats(&mut self) -> Result<()> {
+        liquid_try!(unsafe { dsssframe64sync_reset_framedatastats(self.q) });
+        Ok(())
+    }
+}
+
+impl Drop for DsssFrame64Sync {
+    fn drop(&mut self) {
+        unsafe { dsssframe64sync_destroy(self.q) };
+    }
+}
@@ -0,0 +1,394 @@
+//! Fractional sample delays: `fdelay` and the Farrow filter `firfarrow`.
+
+use liquidizers_sys::*;
+
+use std::os::raw::{c_int, c_uint};
+
+use crate::{created, sealed, Complex32, Error, Result};
+
+/// Sample types with liquid fractional delay implementations: `f32`
+/// (`*_rrrf`) and [`Complex32`] (`*_crcf`).
+pub trait FractionalDelaySample: sealed::Sealed + Copy + Default {
+    #[doc(hidden)]
+    type FDelay;
+    #[doc(hidden)]
+    type Farrow;
+
+    #[doc(hidden)]
+    unsafe fn fdelay_create(nmax: c_uint, m: c_uint, npfb: c_uint) -> *mut Self::FDelay;
+    #[doc(hidden)]
+    unsafe fn fdelay_create_default(nmax: c_uint) -> *mut Self::FDelay;
+    #[doc(hidden)]
+    unsafe fn fdelay_destroy(q: *mut Self::FDelay) -> c_int;
+    #[doc(hidden)]
+    unsafe fn fdelay_reset(q: *mut Self::FDelay) -> c_int;
+    #[doc(hidden)]
+    unsafe fn fdelay_get_delay(q: *mut Self::FDelay) -> f32;
+    #[doc(hidden)]
+    unsafe fn fdelay_set_delay(q: *mut Self::FDelay, delay: f32) -> c_int;
+    #[doc(hidden)]
+    unsafe fn fdelay_get_nmax(q: *mut Self::FDelay) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn fdelay_get_m(q: *mut Self::FDelay) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn fdelay_get_npfb(q: *mut Self::FDelay) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn fdelay_push(q: *mut Self::FDelay, x: Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn fdelay_write(q: *mut Self::FDelay, x: *mut Self, n: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn fdelay_execute(q: *mut Self::FDelay, y: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn fdelay_execute_block(
+        q: *mut Self::FDelay,
+        x: *mut Self,
+        n: c_uint,
+        y: *mut Self,
+    ) -> c_int;
+
+    #[doc(hidden)]
+    unsafe fn farrow_create(h_len: c_uint, p: c_uint, fc: f32, att: f32) -> *mut Self::Farrow;
+    #[doc(hidden)]
+    unsafe fn farrow_destroy(q: *mut Self::Farrow) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_reset(q: *mut Self::Farrow) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_push(q: *mut Self::Farrow, x: Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_set_delay(q: *mut Self::Farrow, mu: f32) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_execute(q: *mut Self::Farrow, y: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_execute_block(
+        q: *mut Self::Farrow,
+        x: *mut Self,
+        n: c_uint,
+        y: *mut Self,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_get_length(q: *mut Self::Farrow) -> c_uint;
+    #[doc(hidden)]
+    unsafe fn farrow_get_coefficients(q: *mut Self::Farrow, h: *mut f32) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_freqresponse(q: *mut Self::Farrow, fc: f32, h: *mut Complex32) -> c_int;
+    #[doc(hidden)]
+    unsafe fn farrow_groupdelay(q: *mut Self::Farrow, fc: f32) -> f32;
+}
+
+macro_rules! impl_fractional_delay_sample {
+    (
+        $t:ty,
+        fdelay: $fd:ty {
+            $fd_create:ident, $fd_create_default:ident, $fd_destroy:ident, $fd_reset:ident,
+            $fd_get_delay:ident, $fd_set_delay:ident, $fd_get_nmax:ident,
+            $fd_get_m:ident, $fd_get_npfb:ident,
+            $fd_push:ident, $fd_write:ident, $fd_execute:ident, $fd_execute_block:ident,
+        },
+        farrow: $ff:ty {
+            $ff_create:ident, $ff_destroy:ident, $ff_reset:ident, $ff_push:ident,
+            $ff_set_delay:ident, $ff_execute:ident, $ff_execute_block:ident,
+            $ff_get_length:ident, $ff_get_coefficients:ident, $ff_freqresponse:ident,
+            $ff_groupdelay:ident,
+        },
+    ) => {
+        impl FractionalDelaySample for $t {
+            type FDelay = $fd;
+            type Farrow = $ff;
+
+            unsafe fn fdelay_create(nmax: c_uint, m: c_uint, npfb: c_uint) -> *mut $fd {
+                $fd_create(nmax, m, npfb)
+            }
+            unsafe fn fdelay_create_default(nmax: c_uint) -> *mut $fd {
+                $fd_create_default(nmax)
+            }
+            unsafe fn fdelay_destroy(q: *mut $fd) -> c_int {
+                $fd_destroy(q)
+            }
+            unsafe fn fdelay_reset(q: *mut $fd) -> c_int {
+                $fd_reset(q)
+            }
+            unsafe fn fdelay_get_delay(q: *mut $fd) -> f32 {
+                $fd_get_delay(q)
+            }
+            unsafe fn fdelay_set_delay(q: *mut $fd, delay: f32) -> c_int {
+                $fd_set_delay(q, delay)
+            }
+            unsafe fn fdelay_get_nmax(q: *mut $fd) -> c_uint {
+                $fd_get_nmax(q)
+            }
+            unsafe fn fdelay_get_m(q: *mut $fd) -> c_uint {
+                $fd_get_m(q)
+            }
+            unsafe fn fdelay_get_npfb(q: *mut $fd) -> c_uint {
+                $fd_get_npfb(q)
+            }
+            unsafe fn fdelay_push(q: *mut $fd, x: $t) -> c_int {
+                $fd_push(q, x)
+            }
+            unsafe fn fdelay_write(q: *mut $fd, x: *mut $t, n: c_uint) -> c_int {
+                $fd_write(q, x, n)
+            }
+            unsafe fn fdelay_execute(q: *mut $fd, y: *mut $t) -> c_int {
+                $fd_execute(q, y)
+            }
+            unsafe fn fdelay_execute_block(
+                q: *mut $fd,
+                x: *mut $t,
+                n: c_uint,
+                y: *mut $t,
+            ) -> c_int {
+                $fd_execute_block(q, x, n, y)
+            }
+
+            unsafe fn farrow_create(h_len: c_uint, p: c_uint, fc: f32, att: f32) -> *mut $ff {
+                $ff_create(h_len, p, fc, att)
+            }
+            unsafe fn farrow_destroy(q: *mut $ff) -> c_int {
+                $ff_destroy(q)
+            }
+            unsafe fn farrow_reset(q: *mut $ff) -> c_int {
+                $ff_reset(q)
+            }
+            unsafe fn farrow_push(q: *mut $ff, x: $t) -> c_int {
+                $ff_push(q, x)
+            }
+            unsafe fn farrow_set_delay(q: *mut $ff, mu: f32) -> c_int {
+                $ff_set_delay(q, mu)
+            }
+            unsafe fn farrow_execute(q: *mut $ff, y: *mut $t) -> c_int {
+                $ff_execute(q, y)
+            }
+            unsafe fn farrow_execute_block(
+                q: *mut $ff,
+                x: *mut $t,
+                n: c_uint,
+                y: *mut $t,
+            ) -> c_int {
+                $ff_execute_block(q, x, n, y)
+            }
+            unsafe fn farrow_get_length(q: *mut $ff) -> c_uint {
+                $ff_get_length(q)
+            }
+            unsafe fn farrow_get_coefficients(q: *mut $ff, h: *mut f32) -> c_int {
+                $ff_get_coefficients(q, h)
+            }
+            unsafe fn farrow_freqresponse(q: *mut $ff, fc: f32, h: *mut Complex32) -> c_int {
+                $ff_freqresponse(q, fc, h)
+            }
+            unsafe fn farrow_groupdelay(q: *mut $ff, fc: f32) -> f32 {
+                $ff_groupdelay(q, fc)
+            }
+        }
+    };
+}
+
+impl_fractional_delay_sample! {
+    f32,
+    fdelay: fdelay_rrrf_s {
+        fdelay_rrrf_create, fdelay_rrrf_create_default, fdelay_rrrf_destroy, fdelay_rrrf_reset,
+        fdelay_rrrf_get_delay, fdelay_rrrf_set_delay, fdelay_rrrf_get_nmax,
+        fdelay_rrrf_get_m, fdelay_rrrf_get_npfb,
+        fdelay_rrrf_push, fdelay_rrrf_write, fdelay_rrrf_execute, fdelay_rrrf_execute_block,
+    },
+    farrow: firfarrow_rrrf_s {
+        firfarrow_rrrf_create, firfarrow_rrrf_destroy, firfarrow_rrrf_reset, firfarrow_rrrf_push,
+        firfarrow_rrrf_set_delay, firfarrow_rrrf_execute, firfarrow_rrrf_execute_block,
+        firfarrow_rrrf_get_length, firfarrow_rrrf_get_coefficients, firfarrow_rrrf_freqresponse,
+        firfarrow_rrrf_groupdelay,
+    },
+}
+
+impl_fractional_delay_sample! {
+    Complex32,
+    fdelay: fdelay_crcf_s {
+        fdelay_crcf_create, fdelay_crcf_create_default, fdelay_crcf_destroy, fdelay_crcf_reset,
+        fdelay_crcf_get_delay, fdelay_crcf_set_delay, fdelay_crcf_get_nmax,
+        fdelay_crcf_get_m, fdelay_crcf_get_npfb,
+        fdelay_crcf_push, fdelay_crcf_write, fdelay_crcf_execute, fdelay_crcf_execute_block,
+    },
+    farrow: firfarrow_crcf_s {
+        firfarrow_crcf_create, firfarrow_crcf_destroy, firfarrow_crcf_reset, firfarrow_crcf_push,
+        firfarrow_crcf_set_delay, firfarrow_crcf_execute, firfarrow_crcf_execute_block,
+        firfarrow_crcf_get_length, firfarrow_crcf_get_coefficients, firfarrow_crcf_freqresponse,
+        firfarrow_crcf_groupdelay,
+    },
+}
+
+/// Delays a signal by an arbitrary, adjustable number of samples using a
+/// polyphase filter bank (`fdelay_rrrf`/`fdelay_crcf`).
+pub struct FractionalDelay<T: FractionalDelaySample> {
+    q: *mut T::FDelay,
+}
+
+unsafe impl<T: FractionalDelaySample> Send for FractionalDelay<T> {}
+
+impl<T: FractionalDelaySample> FractionalDelay<T> {
+    /// Creates a delay of up to `nmax` samples, interpolating with filters of
+    /// semi-length `m` across `npfb` filter bank phases.
+    pub fn create(nmax: u32, m: u32, npfb: u32) -> Result<FractionalDelay<T>> {
+        let q = created(unsafe { T::fdelay_create(nmax, m, npfb) })?;
+        Ok(FractionalDelay { q })
+    }
+
+    /// Creates a delay of up to `nmax` samples with liquid's default filter.
+    pub fn create_default(nmax: u32) -> Result<FractionalDelay<T>> {
+        let q = created(unsafe { T::fdelay_create_default(nmax) })?;
+        Ok(FractionalDelay { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::fdelay_reset(self.q) });
+        Ok(())
+    }
+
+    /// Current delay, in samples.
+    pub fn delay(&self) -> f32 {
+        unsafe { T::fdelay_get_delay(self.q) }
+    }
+
+    /// Sets the delay; fails with [`Error::EIRANGE`] unless it lies in
+    /// `0..=nmax()`.
+    pub fn set_delay(&mut self, delay: f32) -> Result<()> {
+        if !(0.0..=self.nmax() as f32).contains(&delay) {
+            return Err(Error::EIRANGE);
+        }
+        liquid_try!(unsafe { T::fdelay_set_delay(self.q, delay) });
+        Ok(())
+    }
+
+    /// Changes the delay by `delta`; fails with [`Error::EIRANGE`] if the
+    /// result leaves `0..=nmax()`.
+    pub fn adjust_delay(&mut self, delta: f32) -> Result<()> {
+        self.set_delay(self.delay() + delta)
+    }
+
+    /// Largest supported delay, in samples.
+    pub fn nmax(&self) -> u32 {
+        unsafe { T::fdelay_get_nmax(self.q) }
+    }
+
+    /// Semi-length of the interpolating filters.
+    pub fn m(&self) -> u32 {
+        unsafe { T::fdelay_get_m(self.q) }
+    }
+
+    /// Number of filter bank phases, i.e. the delay resolution is `1/npfb()`.
+    pub fn npfb(&self) -> u32 {
+        unsafe { T::fdelay_get_npfb(self.q) }
+    }
+
+    pub fn push(&mut self, x: T) -> Result<()> {
+        liquid_try!(unsafe { T::fdelay_push(self.q, x) });
+        Ok(())
+    }
+
+    pub fn write(&mut self, x: &[T]) -> Result<()> {
+        liquid_try!(unsafe { T::fdelay_write(self.q, x.as_ptr() as *mut _, x.len() as _) });
+        Ok(())
+    }
+
+    /// Computes the delayed output for the most recently pushed sample.
+    pub fn execute(&mut self) -> Result<T> {
+        let mut y = T::default();
+        liquid_try!(unsafe { T::fdelay_execute(self.q, &mut y) });
+        Ok(y)
+    }
+
+    /// Pushes `x` and returns the delayed block.
+    pub fn execute_block(&mut self, x: &[T]) -> Result<Vec<T>> {
+        let mut y = vec![T::default(); x.len()];
+        liquid_try!(unsafe {
+            T::fdelay_execute_block(self.q, x.as_ptr() as *mut _, x.len() as _, y.as_mut_ptr())
+        });
+        Ok(y)
+    }
+}
+
+impl<T: FractionalDelaySample> Drop for FractionalDelay<T> {
+    fn drop(&mut self) {
+        unsafe { T::fdelay_destroy(self.q) };
+    }
+}
+
+/// Low-pass filter whose fractional delay is set by evaluating polynomial
+/// coefficients (`firfarrow_rrrf`/`firfarrow_crcf`).
+pub struct FarrowFilter<T: FractionalDelaySample> {
+    q: *mut T::Farrow,
+}
+
+unsafe impl<T: FractionalDelaySample> Send for FarrowFilter<T> {}
+
+impl<T: FractionalDelaySample> FarrowFilter<T> {
+    /// Creates a filter of `h_len` taps with polynomial order `p`, cutoff `fc`
+    /// and stop-band attenuation `attenuation` dB.
+    pub fn create(h_len: u32, p: u32, fc: f32, attenuation: f32) -> Result<FarrowFilter<T>> {
+        let q = created(unsafe { T::farrow_create(h_len, p, fc, attenuation) })?;
+        Ok(FarrowFilter { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::farrow_reset(self.q) });
+        Ok(())
+    }
+
+    /// Number of filter taps.
+    pub fn len(&self) -> usize {
+        unsafe { T::farrow_get_length(self.q) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets the fractional delay `mu`, in samples.
+    pub fn set_delay(&mut self, mu: f32) -> Result<()> {
+        liquid_try!(unsafe { T::farrow_set_delay(self.q, mu) });
+        Ok(())
+    }
+
+    /// Filter taps for the current delay.
+    pub fn coefficients(&self) -> Vec<f32> {
+        let mut h = vec![0.0; self.len()];
+        unsafe { T::farrow_get_coefficients(self.q, h.as_mut_ptr()) };
+        h
+    }
+
+    /// Frequency response at normalized frequency `fc`.
+    pub fn freqresponse(&self, fc: f32) -> Complex32 {
+        let mut h = Complex32::default();
+        unsafe { T::farrow_freqresponse(self.q, fc, &mut h) };
+        h
+    }
+
+    /// Group delay at normalized frequency `fc`, in samples.
+    pub fn groupdelay(&self, fc: f32) -> f32 {
+        unsafe { T::farrow_groupdelay(self.q, fc) }
+    }
+
+    pub fn push(&mut self, x: T) -> Result<()> {
+        liquid_try!(unsafe { T::farrow_push(self.q, x) });
+        Ok(())
+    }
+
+    /// Computes the output for the most recently pushed sample.
+    pub fn execute(&mut self) -> Result<T> {
+        let mut y = T::default();
+        liquid_try!(unsafe { T::farrow_execute(self.q, &mut y) });
+        Ok(y)
+    }
+
+    /// Pushes `x` and returns the filtered block.
+    pub fn execute_block(&mut self, x: &[T]) -> Result<Vec<T>> {
+        let mut y = vec![T::default(); x.len()];
+        liquid_try!(unsafe {
+            T::farrow_execute_block(self.q, x.as_ptr() as *mut _, x.len() as _, y.as_mut_ptr())
+        });
+        Ok(y)
+    }
+}
+
+impl<T: FractionalDelaySample> Drop for FarrowFilter<T> {
+    fn drop(&mut self) {
+        unsafe { T::farrow_destroy(self.q) };
+    }
+}
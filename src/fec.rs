@@ -0,0 +1,117 @@
+//! Error-detection and forward error-correction scheme identifiers.
+
+use liquidizers_sys::*;
+
+use std::error;
+use std::ffi::CString;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{static_str, Error};
+
+liquid_enum! {
+    /// Cyclic redundancy check / checksum appended to a payload (`crc_scheme`).
+    pub enum CrcScheme: crc_scheme {
+        None = crc_scheme_LIQUID_CRC_NONE,
+        Checksum = crc_scheme_LIQUID_CRC_CHECKSUM,
+        Crc8 = crc_scheme_LIQUID_CRC_8,
+        Crc16 = crc_scheme_LIQUID_CRC_16,
+        Crc24 = crc_scheme_LIQUID_CRC_24,
+        Crc32 = crc_scheme_LIQUID_CRC_32,
+    }
+}
+
+liquid_enum! {
+    /// Forward error-correction code (`fec_scheme`).
+    pub enum FecScheme: fec_scheme {
+        None = fec_scheme_LIQUID_FEC_NONE,
+        Rep3 = fec_scheme_LIQUID_FEC_REP3,
+        Rep5 = fec_scheme_LIQUID_FEC_REP5,
+        Hamming74 = fec_scheme_LIQUID_FEC_HAMMING74,
+        Hamming84 = fec_scheme_LIQUID_FEC_HAMMING84,
+        Hamming128 = fec_scheme_LIQUID_FEC_HAMMING128,
+        Golay2412 = fec_scheme_LIQUID_FEC_GOLAY2412,
+        Secded2216 = fec_scheme_LIQUID_FEC_SECDED2216,
+        Secded3932 = fec_scheme_LIQUID_FEC_SECDED3932,
+        Secded7264 = fec_scheme_LIQUID_FEC_SECDED7264,
+        ConvV27 = fec_scheme_LIQUID_FEC_CONV_V27,
+        ConvV29 = fec_scheme_LIQUID_FEC_CONV_V29,
+        ConvV39 = fec_scheme_LIQUID_FEC_CONV_V39,
+        ConvV615 = fec_scheme_LIQUID_FEC_CONV_V615,
+        ConvV27P23 = fec_scheme_LIQUID_FEC_CONV_V27P23,
+        ConvV27P34 = fec_scheme_LIQUID_FEC_CONV_V27P34,
+        ConvV27P45 = fec_scheme_LIQUID_FEC_CONV_V27P45,
+        ConvV27P56 = fec_scheme_LIQUID_FEC_CONV_V27P56,
+        ConvV27P67 = fec_scheme_LIQUID_FEC_CONV_V27P67,
+        ConvV27P78 = fec_scheme_LIQUID_FEC_CONV_V27P78,
+        ConvV29P23 = fec_scheme_LIQUID_FEC_CONV_V29P23,
+        ConvV29P34 = fec_scheme_LIQUID_FEC_CONV_V29P34,
+        ConvV29P45 = fec_scheme_LIQUID_FEC_CONV_V29P45,
+        ConvV29P56 = fec_scheme_LIQUID_FEC_CONV_V29P56,
+        ConvV29P67 = fec_scheme_LIQUID_FEC_CONV_V29P67,
+        ConvV29P78 = fec_scheme_LIQUID_FEC_CONV_V29P78,
+        RsM8 = fec_scheme_LIQUID_FEC_RS_M8,
+    }
+}
+
+impl CrcScheme {
+    /// Short name used by liquid, e.g. `"crc32"`.
+    pub fn name(self) -> &'static str {
+        unsafe { static_str(crc_scheme_str[self.to_raw() as usize][0]) }
+    }
+
+    /// Number of bytes appended to the payload.
+    pub fn length(self) -> usize {
+        unsafe { crc_get_length(self.to_raw()) as usize }
+    }
+}
+
+impl FecScheme {
+    /// Short name used by liquid, e.g. `"h74"`.
+    pub fn name(self) -> &'static str {
+        unsafe { static_str(fec_scheme_str[self.to_raw() as usize][0]) }
+    }
+
+    /// Code rate.
+    pub fn rate(self) -> f32 {
+        unsafe { fec_get_rate(self.to_raw()) }
+    }
+
+    /// Encoded length in bytes of a `dec_len`-byte message.
+    pub fn encoded_len(self, dec_len: usize) -> usize {
+        unsafe { fec_get_enc_msg_length(self.to_raw(), dec_len as _) as usize }
+    }
+}
+
+impl FromStr for CrcScheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let s = CString::new(s).map_err(|_| Error::EIVAL)?;
+        CrcScheme::from_raw(unsafe { liquid_getopt_str2crc(s.as_ptr()) }).ok_or(Error::EIVAL)
+    }
+}
+
+impl FromStr for FecScheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let s = CString::new(s).map_err(|_| Error::EIVAL)?;
+        FecScheme::from_raw(unsafe { liquid_getopt_str2fec(s.as_ptr()) }).ok_or(Error::EIVAL)
+    }
+}
+
+/// A payload that failed its CRC check. The corrupted payload is kept so
+/// callers can still inspect it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CrcError {
+    pub payload: Vec<u8>,
+}
+
+impl error::Error for CrcError {}
+
+impl fmt::Display for CrcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "payload failed CRC check")
+    }
+}
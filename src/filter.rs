@@ -0,0 +1,288 @@
+//! Finite impulse response filters, their prototypes and length estimation.
+
+use liquidizers_sys::*;
+
+use std::ffi::CString;
+use std::str::FromStr;
+
+use crate::{created, static_str, Complex32, Error};
+
+liquid_enum! {
+    /// Prototype filter shape (`liquid_firfilt_type`).
+    pub enum FirFilterType: liquid_firfilt_type {
+        Kaiser = liquid_firfilt_type_LIQUID_FIRFILT_KAISER,
+        Pm = liquid_firfilt_type_LIQUID_FIRFILT_PM,
+        Rcos = liquid_firfilt_type_LIQUID_FIRFILT_RCOS,
+        Fexp = liquid_firfilt_type_LIQUID_FIRFILT_FEXP,
+        Fsech = liquid_firfilt_type_LIQUID_FIRFILT_FSECH,
+        Farcsech = liquid_firfilt_type_LIQUID_FIRFILT_FARCSECH,
+        Arkaiser = liquid_firfilt_type_LIQUID_FIRFILT_ARKAISER,
+        Rkaiser = liquid_firfilt_type_LIQUID_FIRFILT_RKAISER,
+        Rrc = liquid_firfilt_type_LIQUID_FIRFILT_RRC,
+        HM3 = liquid_firfilt_type_LIQUID_FIRFILT_hM3,
+        GmskTx = liquid_firfilt_type_LIQUID_FIRFILT_GMSKTX,
+        GmskRx = liquid_firfilt_type_LIQUID_FIRFILT_GMSKRX,
+        Rfexp = liquid_firfilt_type_LIQUID_FIRFILT_RFEXP,
+        Rfsech = liquid_firfilt_type_LIQUID_FIRFILT_RFSECH,
+        Rfarcsech = liquid_firfilt_type_LIQUID_FIRFILT_RFARCSECH,
+    }
+}
+
+impl FirFilterType {
+    /// Short name used by liquid, e.g. `"rrcos"`.
+    pub fn name(self) -> &'static str {
+        unsafe { static_str(liquid_firfilt_type_str[self.to_raw() as usize][0]) }
+    }
+}
+
+impl FromStr for FirFilterType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let s = CString::new(s).map_err(|_| Error::EIVAL)?;
+        let raw = unsafe { liquid_getopt_str2firfilt(s.as_ptr()) };
+        FirFilterType::from_raw(raw as _).ok_or(Error::EIVAL)
+    }
+}
+
+/// Requirements for a Kaiser-windowed low-pass filter. Give the cutoff and
+/// any two of the transition bandwidth, stop-band attenuation and length;
+/// [`solve`](Self::solve) estimates the third.
+///
+/// Frequencies are normalized to the sample rate.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct FilterSpec {
+    pub cutoff: f32,
+    pub transition_bw: Option<f32>,
+    pub attenuation_db: Option<f32>,
+    pub len: Option<u32>,
+}
+
+impl FilterSpec {
+    /// Fills in whichever of `transition_bw`, `attenuation_db` and `len` is
+    /// missing (`estimate_req_filter_*`). Fails with [`Error::EICONFIG`] if
+    /// fewer than two are set or any is out of range.
+    pub fn solve(&self) -> crate::Result<FilterSpec> {
+        if !(self.cutoff > 0.0 && self.cutoff < 0.5) {
+            return Err(Error::EICONFIG);
+        }
+        let in_range = |v: Option<f32>, hi: f32| v.is_none_or(|v| v > 0.0 && v < hi);
+        if !in_range(self.transition_bw, 0.5)
+            || !in_range(self.attenuation_db, f32::INFINITY)
+            || self.len == Some(0)
+        {
+            return Err(Error::EICONFIG);
+        }
+        let mut spec = *self;
+        match (self.transition_bw, self.attenuation_db, self.len) {
+            (Some(df), Some(a), None) => {
+                spec.len = Some(unsafe { estimate_req_filter_len(df, a) });
+            }
+            (Some(df), None, Some(n)) => {
+                spec.attenuation_db = Some(unsafe { estimate_req_filter_As(df, n) });
+            }
+            (None, Some(a), Some(n)) => {
+                spec.transition_bw = Some(unsafe { estimate_req_filter_df(a, n) });
+            }
+            (Some(_), Some(_), Some(_)) => {}
+            _ => return Err(Error::EICONFIG),
+        }
+        Ok(spec)
+    }
+
+    /// Kaiser window shape factor for the (solved) attenuation
+    /// (`kaiser_beta_As`).
+    pub fn kaiser_beta(&self) -> crate::Result<f32> {
+        let a = self.solve()?.attenuation_db.unwrap_or_default();
+        Ok(unsafe { kaiser_beta_As(a) })
+    }
+
+    /// Solves the spec and designs the filter (`firfilt_rrrf_create_kaiser`).
+    pub fn design(&self) -> crate::Result<FirFilter> {
+        let spec = self.solve()?;
+        FirFilter::create_kaiser(
+            spec.len.unwrap_or_default(),
+            spec.cutoff,
+            spec.attenuation_db.unwrap_or_default(),
+            0.0,
+        )
+    }
+}
+
+/// Real finite impulse response filter (`firfilt_rrrf`).
+pub struct FirFilter {
+    q: firfilt_rrrf,
+}
+
+unsafe impl Send for FirFilter {}
+
+impl FirFilter {
+    /// Creates a filter from the taps `h`.
+    pub fn create(h: &[f32]) -> crate::Result<FirFilter> {
+        let q = created(unsafe { firfilt_rrrf_create(h.as_ptr() as *mut _, h.len() as _) })?;
+        Ok(FirFilter { q })
+    }
+
+    /// Kaiser-windowed low-pass filter of `n` taps with cutoff `fc`,
+    /// stop-band attenuation `attenuation` dB and fractional delay `mu`.
+    pub fn create_kaiser(n: u32, fc: f32, attenuation: f32, mu: f32) -> crate::Result<FirFilter> {
+        let q = created(unsafe { firfilt_rrrf_create_kaiser(n, fc, attenuation, mu) })?;
+        Ok(FirFilter { q })
+    }
+
+    /// Square-root Nyquist filter with `k` samples per symbol, `m` symbols of
+    /// delay, excess bandwidth `beta` and fractional delay `mu`.
+    pub fn create_rnyquist(
+        ftype: FirFilterType,
+        k: u32,
+        m: u32,
+        beta: f32,
+        mu: f32,
+    ) -> crate::Result<FirFilter> {
+        let q =
+            created(unsafe { firfilt_rrrf_create_rnyquist(ftype.to_raw() as _, k, m, beta, mu) })?;
+        Ok(FirFilter { q })
+    }
+
+    /// Parks-McClellan low-pass filter of `h_len` taps.
+    pub fn create_firdespm(h_len: u32, fc: f32, attenuation: f32) -> crate::Result<FirFilter> {
+        let q = created(unsafe { firfilt_rrrf_create_firdespm(h_len, fc, attenuation) })?;
+        Ok(FirFilter { q })
+    }
+
+    /// Moving average over `n` samples.
+    pub fn create_rect(n: u32) -> crate::Result<FirFilter> {
+        let q = created(unsafe { firfilt_rrrf_create_rect(n) })?;
+        Ok(FirFilter { q })
+    }
+
+    /// DC-blocking filter of `2*m+1` taps.
+    pub fn create_dc_blocker(m: u32, attenuation: f32) -> crate::Result<FirFilter> {
+        let q = created(unsafe { firfilt_rrrf_create_dc_blocker(m, attenuation) })?;
+        Ok(FirFilter { q })
+    }
+
+    /// Notch filter of `2*m+1` taps at frequency `f0`.
+    pub fn create_notch(m: u32, attenuation: f32, f0: f32) -> crate::Result<FirFilter> {
+        let q = created(unsafe { firfilt_rrrf_create_notch(m, attenuation, f0) })?;
+        Ok(FirFilter { q })
+    }
+
+    /// Parks-McClellan half-band filter of `4*m+1` taps with transition
+    /// bandwidth `ft` (`liquid_firdespm_halfband_ft`).
+    pub fn create_halfband_ft(m: u32, ft: f32) -> crate::Result<FirFilter> {
+        let mut h = vec![0.0; 4 * m as usize + 1];
+        liquid_try!(unsafe { liquid_firdespm_halfband_ft(m, ft, h.as_mut_ptr()) });
+        FirFilter::create(&h)
+    }
+
+    /// Parks-McClellan half-band filter of `4*m+1` taps with stop-band
+    /// attenuation `attenuation` dB (`liquid_firdespm_halfband_as`).
+    pub fn create_halfband_as(m: u32, attenuation: f32) -> crate::Result<FirFilter> {
+        let mut h = vec![0.0; 4 * m as usize + 1];
+        liquid_try!(unsafe { liquid_firdespm_halfband_as(m, attenuation, h.as_mut_ptr()) });
+        FirFilter::create(&h)
+    }
+
+    /// Replaces the taps, keeping the buffered samples when the length is
+    /// unchanged; fails with [`Error::EIRANGE`] if `h` is empty.
+    pub fn recreate(&mut self, h: &[f32]) -> crate::Result<()> {
+        if h.is_empty() {
+            return Err(Error::EIRANGE);
+        }
+        self.q =
+            created(unsafe { firfilt_rrrf_recreate(self.q, h.as_ptr() as *mut _, h.len() as _) })?;
+        Ok(())
+    }
+
+    pub fn reset(&mut self) -> crate::Result<()> {
+        liquid_try!(unsafe { firfilt_rrrf_reset(self.q) });
+        Ok(())
+    }
+
+    /// Output gain.
+    pub fn scale(&self) -> f32 {
+        let mut scale = 0.0;
+        unsafe { firfilt_rrrf_get_scale(self.q, &mut scale) };
+        scale
+    }
+
+    pub fn set_scale(&mut self, scale: f32) -> crate::Result<()> {
+        liquid_try!(unsafe { firfilt_rrrf_set_scale(self.q, scale) });
+        Ok(())
+    }
+
+    /// Number of filter taps.
+    pub fn len(&self) -> usize {
+        unsafe { firfilt_rrrf_get_length(self.q) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn coefficients(&self) -> Vec<f32> {
+        let mut h = vec![0.0; self.len()];
+        unsafe { firfilt_rrrf_copy_coefficients(self.q, h.as_mut_ptr()) };
+        h
+    }
+
+    /// Frequency response at normalized frequency `fc`.
+    pub fn freqresponse(&self, fc: f32) -> Complex32 {
+        let mut h = Complex32::default();
+        unsafe { firfilt_rrrf_freqresponse(self.q, fc, &mut h) };
+        h
+    }
+
+    /// Group delay at normalized frequency `fc`, in samples.
+    pub fn groupdelay(&self, fc: f32) -> f32 {
+        unsafe { firfilt_rrrf_groupdelay(self.q, fc) }
+    }
+
+    pub fn push(&mut self, x: f32) -> crate::Result<()> {
+        liquid_try!(unsafe { firfilt_rrrf_push(self.q, x) });
+        Ok(())
+    }
+
+    pub fn write(&mut self, x: &[f32]) -> crate::Result<()> {
+        liquid_try!(unsafe { firfilt_rrrf_write(self.q, x.as_ptr() as *mut _, x.len() as _) });
+        Ok(())
+    }
+
+    /// Computes the output for the samples pushed so far.
+    pub fn execute(&mut self) -> crate::Result<f32> {
+        let mut y = 0.0;
+        liquid_try!(unsafe { firfilt_rrrf_execute(self.q, &mut y) });
+        Ok(y)
+    }
+
+    /// Pushes `x` and returns the output.
+    pub fn execute_one(&mut self, x: f32) -> crate::Result<f32> {
+        let mut y = 0.0;
+        liquid_try!(unsafe { firfilt_rrrf_execute_one(self.q, x, &mut y) });
+        Ok(y)
+    }
+
+    /// Pushes `x` and returns the filtered block.
+    pub fn execute_block(&mut self, x: &[f32]) -> crate::Result<Vec<f32>> {
+        let mut y = vec![0.0; x.len()];
+        liquid_try!(unsafe {
+            firfilt_rrrf_execute_block(self.q, x.as_ptr() as *mut _, x.len() as _, y.as_mut_ptr())
+        });
+        Ok(y)
+    }
+}
+
+impl Clone for FirFilter {
+    fn clone(&self) -> Self {
+        FirFilter {
+            q: unsafe { firfilt_rrrf_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for FirFilter {
+    fn drop(&mut self) {
+        unsafe { firfilt_rrrf_destroy(self.q) };
+    }
+}
@@ -0,0 +1,136 @@
+//! Types shared by the frame generators and synchronizers.
+
+use liquidizers_sys::*;
+
+use std::os::raw::{c_int, c_uchar, c_uint, c_void};
+use std::slice;
+
+use crate::{Complex32, CrcScheme, FecScheme, ModulationScheme};
+
+/// Length of the user header liquid's frame generators use unless told otherwise.
+pub(crate) const DEFAULT_HEADER_LEN: usize = 8;
+
+/// Receiver statistics reported alongside each decoded frame (`framesyncstats_s`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameSyncStats {
+    /// Error vector magnitude, in dB.
+    pub evm: f32,
+    /// Received signal strength, in dB.
+    pub rssi: f32,
+    /// Carrier frequency offset, in radians/sample.
+    pub cfo: f32,
+    /// Received payload symbols after equalization.
+    pub framesyms: Vec<Complex32>,
+    pub mod_scheme: Option<ModulationScheme>,
+    /// Bits per payload symbol.
+    pub mod_bps: u32,
+    pub check: Option<CrcScheme>,
+    pub fec0: Option<FecScheme>,
+    pub fec1: Option<FecScheme>,
+}
+
+impl FrameSyncStats {
+    /// Copies `stats` out of a liquid callback; `framesyms` is only valid for
+    /// the duration of that callback.
+    pub(crate) unsafe fn from_raw(stats: &framesyncstats_s) -> FrameSyncStats {
+        let framesyms = if stats.framesyms.is_null() {
+            Vec::new()
+        } else {
+            slice::from_raw_parts(stats.framesyms, stats.num_framesyms as usize).to_vec()
+        };
+        FrameSyncStats {
+            evm: stats.evm,
+            rssi: stats.rssi,
+            cfo: stats.cfo,
+            framesyms,
+            mod_scheme: ModulationScheme::from_raw(stats.mod_scheme),
+            mod_bps: stats.mod_bps,
+            check: CrcScheme::from_raw(stats.check),
+            fec0: FecScheme::from_raw(stats.fec0),
+            fec1: FecScheme::from_raw(stats.fec1),
+        }
+    }
+}
+
+/// Frame counters accumulated by a synchronizer (`framedatastats_s`).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct FrameDataStats {
+    pub num_frames_detected: u32,
+    pub num_headers_valid: u32,
+    pub num_payloads_valid: u32,
+    pub num_bytes_received: u64,
+}
+
+impl From<framedatastats_s> for FrameDataStats {
+    // `c_ulong` is only 32 bits wide on Windows.
+    #[allow(clippy::useless_conversion)]
+    fn from(stats: framedatastats_s) -> Self {
+        FrameDataStats {
+            num_frames_detected: stats.num_frames_detected,
+            num_headers_valid: stats.num_headers_valid,
+            num_payloads_valid: stats.num_payloads_valid,
+            num_bytes_received: stats.num_bytes_received.into(),
+        }
+    }
+}
+
+/// A frame handed to a synchronizer's callback.
+#[derive(Debug)]
+pub struct Frame<'a> {
+    pub header: &'a [u8],
+    pub header_valid: bool,
+    pub payload: &'a [u8],
+    pub payload_valid: bool,
+    pub stats: FrameSyncStats,
+}
+
+/// State behind the `userdata` pointer handed to liquid's `framesync_callback`.
+pub(crate) struct FrameCallback {
+    pub(crate) header_len: usize,
+    pub(crate) callback: Box<dyn FnMut(Frame<'_>)>,
+}
+
+impl FrameCallback {
+    pub(crate) fn new<F>(header_len: usize, callback: F) -> Box<FrameCallback>
+    where
+        F: FnMut(Frame<'_>) + 'static,
+    {
+        Box::new(FrameCallback {
+            header_len,
+            callback: Box::new(callback),
+        })
+    }
+
+    pub(crate) fn userdata(&mut self) -> *mut c_void {
+        self as *mut FrameCallback as *mut c_void
+    }
+}
+
+unsafe fn bytes<'a>(ptr: *const c_uchar, len: usize) -> &'a [u8] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len)
+    }
+}
+
+pub(crate) unsafe extern "C" fn framesync_trampoline(
+    header: *mut c_uchar,
+    header_valid: c_int,
+    payload: *mut c_uchar,
+    payload_len: c_uint,
+    payload_valid: c_int,
+    stats: framesyncstats_s,
+    userdata: *mut c_void,
+) -> c_int {
+    let state = &mut *(userdata as *mut FrameCallback);
+    let frame = Frame {
+        header: bytes(header, state.header_len),
+        header_valid: header_valid != 0,
+        payload: bytes(payload, payload_len as usize),
+        payload_valid: payload_valid != 0,
+        stats: FrameSyncStats::from_raw(&stats),
+    };
+    (state.callback)(frame);
+    0
+}
@@ -0,0 +1,112 @@
+//! FSK frames: `fskframegen`/`fskframesync`.
+
+use liquidizers_sys::*;
+
+use crate::framing::{framesync_trampoline, FrameCallback, DEFAULT_HEADER_LEN};
+use crate::{created, Complex32, CrcScheme, Error, FecScheme, Frame, Result};
+
+/// FSK frame generator (`fskframegen`). Headers are always 8 bytes.
+pub struct FskFrameGen {
+    q: fskframegen,
+}
+
+unsafe impl Send for FskFrameGen {}
+
+impl FskFrameGen {
+    pub fn create() -> Result<FskFrameGen> {
+        let q = created(unsafe { fskframegen_create() })?;
+        Ok(FskFrameGen { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { fskframegen_reset(self.q) });
+        Ok(())
+    }
+
+    /// Number of samples in the assembled frame.
+    pub fn frame_len(&self) -> usize {
+        unsafe { fskframegen_getframelen(self.q) as usize }
+    }
+
+    /// Assembles a frame and returns all of its samples.
+    pub fn assemble(
+        &mut self,
+        header: &[u8; DEFAULT_HEADER_LEN],
+        payload: &[u8],
+        check: CrcScheme,
+        fec0: FecScheme,
+        fec1: FecScheme,
+    ) -> Result<Vec<Complex32>> {
+        if payload.is_empty() {
+            return Err(Error::EIVAL);
+        }
+        liquid_try!(unsafe {
+            fskframegen_assemble(
+                self.q,
+                header.as_ptr() as *mut _,
+                payload.as_ptr() as *mut _,
+                payload.len() as _,
+                check.to_raw(),
+                fec0.to_raw(),
+                fec1.to_raw(),
+            )
+        });
+        let mut buf = vec![Complex32::default(); self.frame_len()];
+        unsafe { fskframegen_write_samples(self.q, buf.as_mut_ptr(), buf.len() as _) };
+        Ok(buf)
+    }
+}
+
+impl Drop for FskFrameGen {
+    fn drop(&mut self) {
+        unsafe { fskframegen_destroy(self.q) };
+    }
+}
+
+/// FSK frame synchronizer (`fskframesync`).
+pub struct FskFrameSync {
+    q: fskframesync,
+    _callback: Box<FrameCallback>,
+}
+
+impl FskFrameSync {
+    /// Creates a synchronizer that calls `callback` for every detected frame.
+    pub fn create<F>(callback: F) -> Result<FskFrameSync>
+    where
+        F: FnMut(Frame<'_>) + 'static,
+    {
+        let mut callback = FrameCallback::new(DEFAULT_HEADER_LEN, callback);
+        let q = created(unsafe {
+            fskframesync_create(Some(framesync_trampoline), callback.userdata())
+        })?;
+        Ok(FskFrameSync {
+            q,
+            _callback: callback,
+        })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { fskframesync_reset(self.q) });
+        Ok(())
+    }
+
+    /// Pushes a single received sample through the synchronizer.
+    pub fn push(&mut self, x: Complex32) -> Result<()> {
+        liquid_try!(unsafe { fskframesync_execute(self.q, x) });
+        Ok(())
+    }
+
+    /// Pushes received samples through the synchronizer.
+    pub fn execute(&mut self, x: &[Complex32]) -> Result<()> {
+        liquid_try!(unsafe {
+            fskframesync_execute_block(self.q, x.as_ptr() as *mut _, x.len() as _)
+        });
+        Ok(())
+    }
+}
+
+impl Drop for FskFrameSync {
+    fn drop(&mut self) {
+        unsafe { fskframesync_destroy(self.q) };
+    }
+}
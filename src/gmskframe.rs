@@ -0,0 +1,274 @@
+//! GMSK frames: `gmskframegen`/`gmskframesync`.
+
+use liquidizers_sys::*;
+
+use crate::framing::{framesync_trampoline, FrameCallback, DEFAULT_HEADER_LEN};
+use crate::{created, Complex32, CrcScheme, Error, FecScheme, Frame, FrameDataStats, Result};
+
+/// Builder for [`GmskFrameGen`], covering `gmskframegen_create_set` and
+/// `gmskframegen_set_header_len`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GmskFrameGenBuilder {
+    k: u32,
+    m: u32,
+    bt: f32,
+    header_len: usize,
+}
+
+impl Default for GmskFrameGenBuilder {
+    fn default() -> Self {
+        GmskFrameGenBuilder {
+            k: 2,
+            m: 3,
+            bt: 0.5,
+            header_len: DEFAULT_HEADER_LEN,
+        }
+    }
+}
+
+impl GmskFrameGenBuilder {
+    /// Samples per symbol (default 2).
+    pub fn samples_per_symbol(mut self, k: u32) -> Self {
+        self.k = k;
+        self
+    }
+
+    /// Filter delay in symbols (default 3).
+    pub fn filter_delay(mut self, m: u32) -> Self {
+        self.m = m;
+        self
+    }
+
+    /// Bandwidth-time product (default 0.5).
+    pub fn bt(mut self, bt: f32) -> Self {
+        self.bt = bt;
+        self
+    }
+
+    /// User header length in bytes (default 8).
+    pub fn header_len(mut self, len: usize) -> Self {
+        self.header_len = len;
+        self
+    }
+
+    pub fn build(self) -> Result<GmskFrameGen> {
+        let q = created(unsafe { gmskframegen_create_set(self.k, self.m, self.bt) })?;
+        let mut gen = GmskFrameGen {
+            q,
+            header_len: DEFAULT_HEADER_LEN,
+        };
+        if self.header_len != DEFAULT_HEADER_LEN {
+            liquid_try!(unsafe { gmskframegen_set_header_len(gen.q, self.header_len as _) });
+            gen.header_len = self.header_len;
+        }
+        Ok(gen)
+    }
+}
+
+/// GMSK frame generator (`gmskframegen`).
+pub struct GmskFrameGen {
+    q: gmskframegen,
+    header_len: usize,
+}
+
+unsafe impl Send for GmskFrameGen {}
+
+impl GmskFrameGen {
+    /// Creates a generator with liquid's default parameters.
+    pub fn create() -> Result<GmskFrameGen> {
+        let q = created(unsafe { gmskframegen_create() })?;
+        Ok(GmskFrameGen {
+            q,
+            header_len: DEFAULT_HEADER_LEN,
+        })
+    }
+
+    pub fn builder() -> GmskFrameGenBuilder {
+        GmskFrameGenBuilder::default()
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { gmskframegen_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn is_assembled(&self) -> bool {
+        unsafe { gmskframegen_is_assembled(self.q) != 0 }
+    }
+
+    pub fn header_len(&self) -> usize {
+        self.header_len
+    }
+
+    /// Number of samples in the assembled frame.
+    pub fn frame_len(&self) -> usize {
+        unsafe { gmskframegen_getframelen(self.q) as usize }
+    }
+
+    fn load(
+        &mut self,
+        header: &[u8],
+        payload: &[u8],
+        check: CrcScheme,
+        fec0: FecScheme,
+        fec1: FecScheme,
+    ) -> Result<()> {
+        if header.len() != self.header_len {
+            return Err(Error::EIRANGE);
+        }
+        if payload.is_empty() {
+            return Err(Error::EIVAL);
+        }
+        liquid_try!(unsafe {
+            gmskframegen_assemble(
+                self.q,
+                header.as_ptr(),
+                payload.as_ptr(),
+                payload.len() as _,
+                check.to_raw(),
+                fec0.to_raw(),
+                fec1.to_raw(),
+            )
+        });
+        Ok(())
+    }
+
+    /// Assembles a frame and returns all of its samples; `header` must be
+    /// exactly `header_len()` bytes and `payload` non-empty.
+    pub fn assemble(
+        &mut self,
+        header: &[u8],
+        payload: &[u8],
+        check: CrcScheme,
+        fec0: FecScheme,
+        fec1: FecScheme,
+    ) -> Result<Vec<Complex32>> {
+        self.load(header, payload, check, fec0, fec1)?;
+        let mut buf = vec![Complex32::default(); self.frame_len()];
+        // `gmskframegen_write` returns whether the frame is complete rather
+        // than a status code, and `frame_len()` samples always complete it.
+        let complete = unsafe { gmskframegen_write(self.q, buf.as_mut_ptr(), buf.len() as _) };
+        if complete == 0 {
+            return Err(Error::EINT);
+        }
+        Ok(buf)
+    }
+
+    /// Assembles a frame and returns an iterator over its samples in chunks of
+    /// `chunk_len`; the final chunk is zero-padded.
+    pub fn assemble_chunks(
+        &mut self,
+        header: &[u8],
+        payload: &[u8],
+        check: CrcScheme,
+        fec0: FecScheme,
+        fec1: FecScheme,
+        chunk_len: usize,
+    ) -> Result<GmskFrameChunks<'_>> {
+        if chunk_len == 0 {
+            return Err(Error::EIVAL);
+        }
+        self.load(header, payload, check, fec0, fec1)?;
+        Ok(GmskFrameChunks {
+            gen: self,
+            chunk_len,
+            done: false,
+        })
+    }
+}
+
+impl Drop for GmskFrameGen {
+    fn drop(&mut self) {
+        unsafe { gmskframegen_destroy(self.q) };
+    }
+}
+
+/// Iterator returned by [`GmskFrameGen::assemble_chunks`], wrapping `gmskframegen_write`.
+pub struct GmskFrameChunks<'a> {
+    gen: &'a mut GmskFrameGen,
+    chunk_len: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for GmskFrameChunks<'a> {
+    type Item = Vec<Complex32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut buf = vec![Complex32::default(); self.chunk_len];
+        let complete = unsafe { gmskframegen_write(self.gen.q, buf.as_mut_ptr(), buf.len() as _) };
+        self.done = complete != 0;
+        Some(buf)
+    }
+}
+
+/// GMSK frame synchronizer (`gmskframesync`).
+pub struct GmskFrameSync {
+    q: gmskframesync,
+    callback: Box<FrameCallback>,
+}
+
+impl GmskFrameSync {
+    /// Creates a synchronizer with liquid's default parameters that calls
+    /// `callback` for every detected frame.
+    pub fn create<F>(callback: F) -> Result<GmskFrameSync>
+    where
+        F: FnMut(Frame<'_>) + 'static,
+    {
+        let mut callback = FrameCallback::new(DEFAULT_HEADER_LEN, callback);
+        let q = created(unsafe {
+            gmskframesync_create(Some(framesync_trampoline), callback.userdata())
+        })?;
+        Ok(GmskFrameSync { q, callback })
+    }
+
+    /// Creates a synchronizer matching a generator built with the same `k`, `m` and `bt`.
+    pub fn create_set<F>(k: u32, m: u32, bt: f32, callback: F) -> Result<GmskFrameSync>
+    where
+        F: FnMut(Frame<'_>) + 'static,
+    {
+        let mut callback = FrameCallback::new(DEFAULT_HEADER_LEN, callback);
+        let q = created(unsafe {
+            gmskframesync_create_set(k, m, bt, Some(framesync_trampoline), callback.userdata())
+        })?;
+        Ok(GmskFrameSync { q, callback })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { gmskframesync_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn is_frame_open(&self) -> bool {
+        unsafe { gmskframesync_is_frame_open(self.q) != 0 }
+    }
+
+    pub fn set_header_len(&mut self, len: usize) -> Result<()> {
+        liquid_try!(unsafe { gmskframesync_set_header_len(self.q, len as _) });
+        self.callback.header_len = len;
+        Ok(())
+    }
+
+    /// Pushes received samples through the synchronizer.
+    pub fn execute(&mut self, x: &[Complex32]) -> Result<()> {
+        liquid_try!(unsafe { gmskframesync_execute(self.q, x.as_ptr() as *mut _, x.len() as _) });
+        Ok(())
+    }
+
+    pub fn framedatastats(&self) -> FrameDataStats {
+        unsafe { gmskframesync_get_framedatastats(self.q) }.into()
+    }
+
+    pub fn reset_framedatastats(&mut self) -> Result<()> {
+        liquid_try!(unsafe { gmskframesync_reset_framedatastats(self.q) });
+        Ok(())
+    }
+}
+
+impl Drop for GmskFrameSync {
+    fn drop(&mut self) {
+        unsafe { gmskframesync_destroy(self.q) };
+    }
+}
@@ -0,0 +1,84 @@
+//! Infinite impulse response filter (`iirfilt_rrrf`).
+
+use liquidizers_sys::*;
+
+use crate::{created, Result};
+
+/// Real infinite impulse response filter in direct form (`iirfilt_rrrf`).
+pub struct IirFilter {
+    q: iirfilt_rrrf,
+}
+
+unsafe impl Send for IirFilter {}
+
+impl IirFilter {
+    /// Creates a filter with feed-forward coefficients `b` and feedback
+    /// coefficients `a`, `a[0]` normally being 1.
+    pub fn create(b: &[f32], a: &[f32]) -> Result<IirFilter> {
+        let q = created(unsafe {
+            iirfilt_rrrf_create(
+                b.as_ptr() as *mut _,
+                b.len() as _,
+                a.as_ptr() as *mut _,
+                a.len() as _,
+            )
+        })?;
+        Ok(IirFilter { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { iirfilt_rrrf_reset(self.q) });
+        Ok(())
+    }
+
+    /// Output gain.
+    pub fn scale(&self) -> f32 {
+        let mut scale = 0.0;
+        unsafe { iirfilt_rrrf_get_scale(self.q, &mut scale) };
+        scale
+    }
+
+    pub fn set_scale(&mut self, scale: f32) -> Result<()> {
+        liquid_try!(unsafe { iirfilt_rrrf_set_scale(self.q, scale) });
+        Ok(())
+    }
+
+    /// Filter order plus one.
+    pub fn len(&self) -> usize {
+        unsafe { iirfilt_rrrf_get_length(self.q) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes `x` and returns the output.
+    pub fn execute(&mut self, x: f32) -> Result<f32> {
+        let mut y = 0.0;
+        liquid_try!(unsafe { iirfilt_rrrf_execute(self.q, x, &mut y) });
+        Ok(y)
+    }
+
+    /// Pushes `x` and returns the filtered block.
+    pub fn execute_block(&mut self, x: &[f32]) -> Result<Vec<f32>> {
+        let mut y = vec![0.0; x.len()];
+        liquid_try!(unsafe {
+            iirfilt_rrrf_execute_block(self.q, x.as_ptr() as *mut _, x.len() as _, y.as_mut_ptr())
+        });
+        Ok(y)
+    }
+}
+
+impl Clone for IirFilter {
+    fn clone(&self) -> Self {
+        IirFilter {
+            q: unsafe { iirfilt_rrrf_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for IirFilter {
+    fn drop(&mut self) {
+        unsafe { iirfilt_rrrf_destroy(self.q) };
+    }
+}
@@ -0,0 +1,79 @@
+//! Block interleaving of bytes and soft bits (`interleaver`).
+
+use liquidizers_sys::*;
+
+use crate::{check_len, created, Result};
+
+/// Byte-block interleaver (`interleaver`), spreading burst errors across a
+/// message before FEC decoding.
+pub struct Interleaver {
+    q: interleaver,
+    n: usize,
+}
+
+unsafe impl Send for Interleaver {}
+
+impl Interleaver {
+    /// Creates an interleaver for messages of `n` bytes.
+    pub fn create(n: usize) -> Result<Interleaver> {
+        let q = created(unsafe { interleaver_create(n as _) })?;
+        Ok(Interleaver { q, n })
+    }
+
+    /// Message length in bytes.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Sets the number of permutation passes; zero disables interleaving.
+    pub fn set_depth(&mut self, depth: u32) -> Result<()> {
+        liquid_try!(unsafe { interleaver_set_depth(self.q, depth) });
+        Ok(())
+    }
+
+    /// Interleaves `msg` in place; fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless it is `len()` bytes
+    /// long.
+    pub fn encode(&mut self, msg: &mut [u8]) -> Result<()> {
+        check_len(msg.len(), self.n)?;
+        let mut dec = msg.to_vec();
+        liquid_try!(unsafe { interleaver_encode(self.q, dec.as_mut_ptr(), msg.as_mut_ptr()) });
+        Ok(())
+    }
+
+    /// Inverse of [`encode`](Self::encode).
+    pub fn decode(&mut self, msg: &mut [u8]) -> Result<()> {
+        check_len(msg.len(), self.n)?;
+        let mut enc = msg.to_vec();
+        liquid_try!(unsafe { interleaver_decode(self.q, enc.as_mut_ptr(), msg.as_mut_ptr()) });
+        Ok(())
+    }
+
+    /// Interleaves soft bits in place, one byte per bit; fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless `msg` is `8 * len()`
+    /// bytes long.
+    pub fn encode_soft(&mut self, msg: &mut [u8]) -> Result<()> {
+        check_len(msg.len(), 8 * self.n)?;
+        let mut dec = msg.to_vec();
+        liquid_try!(unsafe { interleaver_encode_soft(self.q, dec.as_mut_ptr(), msg.as_mut_ptr()) });
+        Ok(())
+    }
+
+    /// Inverse of [`encode_soft`](Self::encode_soft).
+    pub fn decode_soft(&mut self, msg: &mut [u8]) -> Result<()> {
+        check_len(msg.len(), 8 * self.n)?;
+        let mut enc = msg.to_vec();
+        liquid_try!(unsafe { interleaver_decode_soft(self.q, enc.as_mut_ptr(), msg.as_mut_ptr()) });
+        Ok(())
+    }
+}
+
+impl Drop for Interleaver {
+    fn drop(&mut self) {
+        unsafe { interleaver_destroy(self.q) };
+    }
+}
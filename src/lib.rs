@@ -1,10 +1,12 @@
 //! Module: liquid-dsp
 
 #![allow(trivial_numeric_casts)]
+#![allow(non_upper_case_globals)]
 
 use libc::{c_int, c_long, c_short};
 
 use std::ffi;
+use std::fmt;
 use std::marker::PhantomData;
 use std::os::raw::c_void;
 
@@ -15,6 +17,130 @@ use std::{mem, ptr, str};
 
 use liquidizers_sys::*;
 
+macro_rules! liquid_try {
+    ($($tt:tt)*) => {{
+        let rc = $($tt)*;
+        if rc != crate::LIQUID_OK {
+            return Err(crate::Error::from_raw(rc as _));
+        }
+    }};
+}
+
+macro_rules! liquid_enum {
+    (
+        $(#[$attr:meta])*
+        pub enum $name:ident: $raw:ty {
+            $($(#[$vattr:meta])* $variant:ident = $value:path,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+        pub enum $name {
+            $($(#[$vattr])* $variant,)*
+        }
+
+        impl $name {
+            pub fn from_raw(raw: $raw) -> Option<$name> {
+                match raw {
+                    $($value => Some($name::$variant),)*
+                    _ => None,
+                }
+            }
+
+            pub fn to_raw(self) -> $raw {
+                match self {
+                    $($name::$variant => $value,)*
+                }
+            }
+
+            /// All variants, in liquid's declaration order.
+            pub fn all() -> &'static [$name] {
+                &[$($name::$variant,)*]
+            }
+        }
+    };
+}
+
+pub mod audio;
+mod autocorr;
+pub mod bits;
+mod bpacket;
+mod buffer;
+mod cpfsk;
+mod dsssframe;
+mod fdelay;
+mod fec;
+mod filter;
+mod framing;
+mod fskframe;
+mod gmskframe;
+mod iirfilt;
+mod interleaver;
+mod lpc;
+pub mod math;
+mod matrix;
+mod modem;
+mod msource;
+mod optim;
+mod ordfilt;
+mod packetmodem;
+mod poly;
+mod quantizer;
+pub mod random;
+mod scrambler;
+mod sequence;
+mod smatrix;
+mod symstream;
+mod window;
+
+pub use crate::autocorr::{AutoCorrelator, AutoCorrelatorSample};
+pub use crate::bpacket::{BPacket, BPacketGen, BPacketSync};
+pub use crate::buffer::{
+    BufferSample, CircularBuffer, CircularBufferIter, DelayLine, SlidingWindow,
+};
+pub use crate::cpfsk::{
+    CpfskDem, CpfskFilter, CpfskMod, FskDem, FskMod, GmskDem, GmskMod, ModulatedSamples,
+    SymbolModulator,
+};
+pub use crate::dsssframe::{
+    DsssFrame64Gen, DsssFrame64Sync, DsssFrameGen, DsssFrameGenProps, DsssFrameSync,
+};
+pub use crate::fdelay::{FarrowFilter, FractionalDelay, FractionalDelaySample};
+pub use crate::fec::{CrcError, CrcScheme, FecScheme};
+pub use crate::filter::{FilterSpec, FirFilter, FirFilterType};
+pub use crate::framing::{Frame, FrameDataStats, FrameSyncStats};
+pub use crate::fskframe::{FskFrameGen, FskFrameSync};
+pub use crate::gmskframe::{GmskFrameChunks, GmskFrameGen, GmskFrameGenBuilder, GmskFrameSync};
+pub use crate::iirfilt::IirFilter;
+pub use crate::interleaver::Interleaver;
+pub use crate::lpc::{levinson, lpc, LpcModel};
+pub use crate::matrix::{LuDecomposition, Matrix, MatrixElement, QrDecomposition};
+pub use crate::modem::ModulationScheme;
+pub use crate::msource::{MultiSource, Source, SourceId};
+pub use crate::optim::{
+    test_functions, Chromosome, GaSearch, GradSearch, OptimDirection, Optimizer, Optimum, QnSearch,
+    Qs1dSearch,
+};
+pub use crate::ordfilt::OrderFilter;
+pub use crate::packetmodem::{PacketModem, PilotFrameGen, PilotFrameSync};
+pub use crate::poly::{Polynomial, PolynomialElement};
+pub use crate::quantizer::{
+    quantize_adc, quantize_dac, AdcModel, CompanderType, Quantizer, QuantizerSample,
+};
+pub use crate::scrambler::Scrambler;
+pub use crate::sequence::{BSequence, MSequence};
+pub use crate::smatrix::{SparseElement, SparseMatrix};
+pub use crate::symstream::{Dds, ResampledSymbolStream, SymbolStream, Synth};
+pub use crate::window::{WindowMetrics, WindowType};
+
+const LIQUID_OK: c_int = liquid_error_code_LIQUID_OK as c_int;
+
+/// Single precision complex sample, layout compatible with liquid's `float complex`.
+pub type Complex32 = liquid_float_complex;
+
+/// Double precision complex sample, layout compatible with liquid's `double complex`.
+pub type Complex64 = liquid_double_complex;
+
 pub fn version() -> String {
     unsafe {
         let cstr = ffi::CStr::from_ptr(liquidizers_sys::liquid_libversion());
@@ -22,5 +148,111 @@ pub fn version() -> String {
     }
 }
 
-// `liquid`-specific Result type.
-// pub type Result<T> = result::Result<T, Error>;
+/// `liquid`-specific Result type.
+pub type Result<T> = result::Result<T, Error>;
+
+/// `liquid` error codes, as returned by the library's `int` status functions.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Error {
+    EINT = liquid_error_code_LIQUID_EINT as isize,
+    EIOBJ = liquid_error_code_LIQUID_EIOBJ as isize,
+    EICONFIG = liquid_error_code_LIQUID_EICONFIG as isize,
+    EIVAL = liquid_error_code_LIQUID_EIVAL as isize,
+    EIRANGE = liquid_error_code_LIQUID_EIRANGE as isize,
+    EIMODE = liquid_error_code_LIQUID_EIMODE as isize,
+    EUMODE = liquid_error_code_LIQUID_EUMODE as isize,
+    ENOINIT = liquid_error_code_LIQUID_ENOINIT as isize,
+    EIMEM = liquid_error_code_LIQUID_EIMEM as isize,
+    EIO = liquid_error_code_LIQUID_EIO as isize,
+    ENOCONV = liquid_error_code_LIQUID_ENOCONV as isize,
+    ENOIMP = liquid_error_code_LIQUID_ENOIMP as isize,
+}
+
+impl Error {
+    pub fn from_raw(raw: liquid_error_code) -> Error {
+        match raw {
+            liquid_error_code_LIQUID_EIOBJ => Error::EIOBJ,
+            liquid_error_code_LIQUID_EICONFIG => Error::EICONFIG,
+            liquid_error_code_LIQUID_EIVAL => Error::EIVAL,
+            liquid_error_code_LIQUID_EIRANGE => Error::EIRANGE,
+            liquid_error_code_LIQUID_EIMODE => Error::EIMODE,
+            liquid_error_code_LIQUID_EUMODE => Error::EUMODE,
+            liquid_error_code_LIQUID_ENOINIT => Error::ENOINIT,
+            liquid_error_code_LIQUID_EIMEM => Error::EIMEM,
+            liquid_error_code_LIQUID_EIO => Error::EIO,
+            liquid_error_code_LIQUID_ENOCONV => Error::ENOCONV,
+            liquid_error_code_LIQUID_ENOIMP => Error::ENOIMP,
+            _ => Error::EINT,
+        }
+    }
+
+    pub fn to_raw(self) -> liquid_error_code {
+        self as liquid_error_code
+    }
+
+    pub fn message(self) -> &'static str {
+        unsafe {
+            let s = liquid_error_info(self.to_raw());
+            let v: &'static [u8] = ffi::CStr::from_ptr(s).to_bytes();
+            str::from_utf8(v).unwrap_or("unknown error")
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn description(&self) -> &str {
+        self.message()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // FIXME: An unquoted string is not a good `Debug` output.
+        write!(f, "{}", self.message())
+    }
+}
+
+mod sealed {
+    /// Restricts the sample-type traits to the types liquid implements.
+    pub trait Sealed {}
+
+    impl Sealed for bool {}
+    impl Sealed for u8 {}
+    impl Sealed for i16 {}
+    impl Sealed for f32 {}
+    impl Sealed for f64 {}
+    impl Sealed for crate::Complex32 {}
+    impl Sealed for crate::Complex64 {}
+}
+
+/// Wraps a freshly created liquid object, mapping a NULL handle (liquid's way of
+/// rejecting a configuration) to `Error::EICONFIG`.
+fn created<T>(q: *mut T) -> Result<*mut T> {
+    if q.is_null() {
+        Err(Error::EICONFIG)
+    } else {
+        Ok(q)
+    }
+}
+
+/// Fails with `Error::EIRANGE` unless a buffer of `len` elements has the
+/// `expected` length.
+pub(crate) fn check_len(len: usize, expected: usize) -> Result<()> {
+    if len == expected {
+        Ok(())
+    } else {
+        Err(Error::EIRANGE)
+    }
+}
+
+/// Borrows one of liquid's static C strings (scheme names and the like).
+unsafe fn static_str(s: *const std::os::raw::c_char) -> &'static str {
+    ffi::CStr::from_ptr(s).to_str().unwrap_or("unknown")
+}
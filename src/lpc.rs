@@ -0,0 +1,95 @@
+//! Linear prediction (`liquid_lpc`, `liquid_levinson`).
+
+use liquidizers_sys::*;
+
+use crate::{Error, FirFilter, IirFilter, Result};
+
+/// All-pole model `x[n] = -a[1] x[n-1] - ... - a[p] x[n-p] + e[n]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LpcModel {
+    /// Prediction polynomial, `p + 1` coefficients with `a[0] = 1`.
+    pub a: Vec<f32>,
+    /// Prediction error variance after each recursion step, `p + 1` values;
+    /// the last is the model's.
+    pub g: Vec<f32>,
+}
+
+/// Fits an order-`p` model to `x`; fails with [`Error::EIRANGE`] unless
+/// `0 < p < x.len()`.
+pub fn lpc(x: &[f32], p: usize) -> Result<LpcModel> {
+    if p == 0 || p >= x.len() {
+        return Err(Error::EIRANGE);
+    }
+    let mut a = vec![0.0; p + 1];
+    let mut g = vec![0.0; p + 1];
+    unsafe {
+        liquid_lpc(
+            x.as_ptr() as *mut _,
+            x.len() as _,
+            p as _,
+            a.as_mut_ptr(),
+            g.as_mut_ptr(),
+        )
+    };
+    LpcModel::checked(a, g)
+}
+
+/// Solves for the order-`r.len() - 1` model with autocorrelation `r` by
+/// Levinson-Durbin recursion. Fails with [`Error::EIRANGE`] if `r` has fewer
+/// than two lags and [`Error::EIVAL`] if `r[0]` is not positive.
+pub fn levinson(r: &[f32]) -> Result<LpcModel> {
+    if r.len() < 2 {
+        return Err(Error::EIRANGE);
+    }
+    if r[0].is_nan() || r[0] <= 0.0 {
+        return Err(Error::EIVAL);
+    }
+    let p = r.len() - 1;
+    let mut a = vec![0.0; p + 1];
+    let mut e = vec![0.0; p + 1];
+    unsafe { liquid_levinson(r.as_ptr() as *mut _, p as _, a.as_mut_ptr(), e.as_mut_ptr()) };
+    LpcModel::checked(a, e)
+}
+
+impl LpcModel {
+    /// Rejects the non-finite results of singular input.
+    fn checked(a: Vec<f32>, g: Vec<f32>) -> Result<LpcModel> {
+        if a.iter().chain(&g).all(|v| v.is_finite()) {
+            Ok(LpcModel { a, g })
+        } else {
+            Err(Error::EIVAL)
+        }
+    }
+
+    /// Model order `p`.
+    pub fn order(&self) -> usize {
+        self.a.len().saturating_sub(1)
+    }
+
+    /// Gain of the synthesis filter, the square root of the final error
+    /// variance.
+    pub fn gain(&self) -> f32 {
+        self.g.last().map_or(0.0, |g| g.sqrt())
+    }
+
+    /// Whitening filter `A(z)`, turning the signal into its prediction error.
+    pub fn analysis_filter(&self) -> Result<FirFilter> {
+        FirFilter::create(&self.a)
+    }
+
+    /// Synthesis filter `gain / A(z)`, shaping white excitation into the
+    /// modelled spectrum.
+    pub fn synthesis_filter(&self) -> Result<IirFilter> {
+        IirFilter::create(&[self.gain()], &self.a)
+    }
+
+    /// Prediction error of `x` under the model.
+    pub fn residual(&self, x: &[f32]) -> Result<Vec<f32>> {
+        self.analysis_filter()?.execute_block(x)
+    }
+
+    /// Runs `excitation` through a fresh synthesis filter.
+    pub fn synthesize(&self, excitation: &[f32]) -> Result<Vec<f32>> {
+        self.synthesis_filter()?.execute_block(excitation)
+    }
+}
@@ -0,0 +1,177 @@
+//! Special functions (`liquid_gammaf`, `liquid_besselif`, `liquid_MarcumQf`,
+//! ...) with domain checks, and the error-rate and detection curves built on
+//! them.
+//!
+//! Everything here is `f32` only. liquid implements these functions in
+//! single precision and has no double-precision versions, so `f64` entry
+//! points could only round their arguments to `f32` and widen the result,
+//! suggesting accuracy they cannot deliver. Callers holding `f64` values
+//! should convert explicitly so the loss of precision stays visible.
+
+use liquidizers_sys::*;
+
+use crate::{Error, Result};
+
+fn check_finite(values: &[f32]) -> Result<()> {
+    if values.iter().all(|v| v.is_finite()) {
+        Ok(())
+    } else {
+        Err(Error::EIVAL)
+    }
+}
+
+fn check_range(valid: bool) -> Result<()> {
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::EIRANGE)
+    }
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 10.0)
+}
+
+/// Gamma function; fails with [`Error::EIRANGE`] at the poles `0, -1, -2,
+/// ...`.
+pub fn gamma(z: f32) -> Result<f32> {
+    check_finite(&[z])?;
+    check_range(z > 0.0 || z.fract() != 0.0)?;
+    Ok(unsafe { liquid_gammaf(z) })
+}
+
+/// Natural logarithm of the gamma function; requires `z > 0`.
+pub fn lngamma(z: f32) -> Result<f32> {
+    check_finite(&[z])?;
+    check_range(z > 0.0)?;
+    Ok(unsafe { liquid_lngammaf(z) })
+}
+
+/// Lower incomplete gamma function `γ(z, alpha)`, the integral of
+/// `t^(z-1) e^-t` from 0 to `alpha`; requires `z > 0` and `alpha >= 0`.
+pub fn lower_gamma(z: f32, alpha: f32) -> Result<f32> {
+    check_finite(&[z, alpha])?;
+    check_range(z > 0.0 && alpha >= 0.0)?;
+    Ok(unsafe { liquid_lowergammaf(z, alpha) })
+}
+
+/// Upper incomplete gamma function `Γ(z, alpha)`, the integral of
+/// `t^(z-1) e^-t` from `alpha` to infinity; requires `z > 0` and
+/// `alpha >= 0`.
+pub fn upper_gamma(z: f32, alpha: f32) -> Result<f32> {
+    check_finite(&[z, alpha])?;
+    check_range(z > 0.0 && alpha >= 0.0)?;
+    Ok(unsafe { liquid_uppergammaf(z, alpha) })
+}
+
+/// Natural logarithm of [`lower_gamma`].
+pub fn ln_lower_gamma(z: f32, alpha: f32) -> Result<f32> {
+    check_finite(&[z, alpha])?;
+    check_range(z > 0.0 && alpha > 0.0)?;
+    Ok(unsafe { liquid_lnlowergammaf(z, alpha) })
+}
+
+/// Natural logarithm of [`upper_gamma`].
+pub fn ln_upper_gamma(z: f32, alpha: f32) -> Result<f32> {
+    check_finite(&[z, alpha])?;
+    check_range(z > 0.0 && alpha >= 0.0)?;
+    Ok(unsafe { liquid_lnuppergammaf(z, alpha) })
+}
+
+/// `n!`, which overflows `f32` for `n > 34`.
+pub fn factorial(n: u32) -> f32 {
+    unsafe { liquid_factorialf(n) }
+}
+
+/// Binomial coefficient; fails with [`Error::EIRANGE`] if `k > n`.
+pub fn nchoosek(n: u32, k: u32) -> Result<f32> {
+    check_range(k <= n)?;
+    Ok(unsafe { liquid_nchoosek(n, k) })
+}
+
+/// Modified Bessel function of the first kind `I_nu(z)`; requires
+/// `nu >= 0`.
+pub fn bessel_i(nu: f32, z: f32) -> Result<f32> {
+    check_finite(&[nu, z])?;
+    check_range(nu >= 0.0)?;
+    Ok(unsafe { liquid_besselif(nu, z) })
+}
+
+/// Natural logarithm of [`bessel_i`]; requires `nu >= 0` and `z > 0`.
+pub fn ln_bessel_i(nu: f32, z: f32) -> Result<f32> {
+    check_finite(&[nu, z])?;
+    check_range(nu >= 0.0 && z > 0.0)?;
+    Ok(unsafe { liquid_lnbesselif(nu, z) })
+}
+
+/// `I_0(z)`.
+pub fn bessel_i0(z: f32) -> Result<f32> {
+    check_finite(&[z])?;
+    Ok(unsafe { liquid_besseli0f(z) })
+}
+
+/// Bessel function of the first kind `J_nu(z)`; requires `nu >= 0`.
+pub fn bessel_j(nu: f32, z: f32) -> Result<f32> {
+    check_finite(&[nu, z])?;
+    check_range(nu >= 0.0)?;
+    Ok(unsafe { liquid_besseljf(nu, z) })
+}
+
+/// `J_0(z)`.
+pub fn bessel_j0(z: f32) -> Result<f32> {
+    check_finite(&[z])?;
+    Ok(unsafe { liquid_besselj0f(z) })
+}
+
+/// Gaussian tail probability `Q(z)`.
+pub fn q(z: f32) -> Result<f32> {
+    check_finite(&[z])?;
+    Ok(unsafe { liquid_Qf(z) })
+}
+
+/// Generalized Marcum Q-function `Q_m(alpha, beta)`; requires `m >= 1` and
+/// non-negative `alpha` and `beta`.
+pub fn marcum_q(m: i32, alpha: f32, beta: f32) -> Result<f32> {
+    check_finite(&[alpha, beta])?;
+    check_range(m >= 1 && alpha >= 0.0 && beta >= 0.0)?;
+    Ok(unsafe { liquid_MarcumQf(m, alpha, beta) })
+}
+
+/// First-order Marcum Q-function `Q_1(alpha, beta)`; requires non-negative
+/// `alpha` and `beta`.
+pub fn marcum_q1(alpha: f32, beta: f32) -> Result<f32> {
+    check_finite(&[alpha, beta])?;
+    check_range(alpha >= 0.0 && beta >= 0.0)?;
+    Ok(unsafe { liquid_MarcumQ1f(alpha, beta) })
+}
+
+/// Bit error rate of coherent BPSK at `ebn0_db`, `Q(sqrt(2 Eb/N0))`.
+pub fn ber_bpsk(ebn0_db: f32) -> Result<f32> {
+    check_finite(&[ebn0_db])?;
+    q((2.0 * db_to_linear(ebn0_db)).sqrt())
+}
+
+/// Bit error rate of differentially detected BPSK at `ebn0_db`,
+/// `exp(-Eb/N0) / 2`.
+pub fn ber_dbpsk(ebn0_db: f32) -> Result<f32> {
+    check_finite(&[ebn0_db])?;
+    Ok(0.5 * (-db_to_linear(ebn0_db)).exp())
+}
+
+/// Bit error rate of non-coherently detected binary orthogonal FSK at
+/// `ebn0_db`, `exp(-Eb/2N0) / 2`.
+pub fn ber_noncoherent_fsk(ebn0_db: f32) -> Result<f32> {
+    check_finite(&[ebn0_db])?;
+    Ok(0.5 * (-0.5 * db_to_linear(ebn0_db)).exp())
+}
+
+/// Probability of detecting a steady sinusoid at `snr_db` with an envelope
+/// detector whose threshold gives false-alarm probability `pfa`,
+/// `Q_1(sqrt(2 SNR), sqrt(-2 ln pfa))`. `pfa` must lie in `(0, 1)`.
+pub fn detection_probability(snr_db: f32, pfa: f32) -> Result<f32> {
+    check_finite(&[snr_db, pfa])?;
+    check_range(pfa > 0.0 && pfa < 1.0)?;
+    let alpha = (2.0 * db_to_linear(snr_db)).sqrt();
+    let beta = (-2.0 * pfa.ln()).sqrt();
+    marcum_q1(alpha, beta)
+}
@@ -0,0 +1,582 @@
+//! Dense linear algebra over liquid's `matrix`/`matrixf`/`matrixc`/`matrixcf`
+//! routines.
+
+use liquidizers_sys::*;
+
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+use std::os::raw::{c_int, c_uint};
+use std::ptr;
+
+use crate::{sealed, Complex32, Complex64, Error, Result};
+
+/// Element types with liquid matrix implementations: `f64` (`matrix`), `f32`
+/// (`matrixf`), [`Complex64`] (`matrixc`) and [`Complex32`] (`matrixcf`).
+pub trait MatrixElement: sealed::Sealed + Copy + Default + PartialEq {
+    #[doc(hidden)]
+    fn is_finite(self) -> bool;
+
+    #[doc(hidden)]
+    unsafe fn matrix_add(x: *mut Self, y: *mut Self, z: *mut Self, r: c_uint, c: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_sub(x: *mut Self, y: *mut Self, z: *mut Self, r: c_uint, c: c_uint) -> c_int;
+    #[doc(hidden)]
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn matrix_mul(
+        x: *mut Self,
+        rx: c_uint,
+        cx: c_uint,
+        y: *mut Self,
+        ry: c_uint,
+        cy: c_uint,
+        z: *mut Self,
+        rz: c_uint,
+        cz: c_uint,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_det(x: *mut Self, r: c_uint, c: c_uint) -> Self;
+    #[doc(hidden)]
+    unsafe fn matrix_trans(x: *mut Self, r: c_uint, c: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_hermitian(x: *mut Self, r: c_uint, c: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_eye(x: *mut Self, n: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_inv(x: *mut Self, r: c_uint, c: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_linsolve(a: *mut Self, n: c_uint, b: *mut Self, x: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_cgsolve(a: *mut Self, n: c_uint, b: *mut Self, x: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_chol(a: *mut Self, n: c_uint, l: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_ludecomp_crout(
+        x: *mut Self,
+        rx: c_uint,
+        cx: c_uint,
+        l: *mut Self,
+        u: *mut Self,
+        p: *mut Self,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_ludecomp_doolittle(
+        x: *mut Self,
+        rx: c_uint,
+        cx: c_uint,
+        l: *mut Self,
+        u: *mut Self,
+        p: *mut Self,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_qrdecomp_gramschmidt(
+        a: *mut Self,
+        m: c_uint,
+        n: c_uint,
+        q: *mut Self,
+        r: *mut Self,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn matrix_gramschmidt(a: *mut Self, r: c_uint, c: c_uint, v: *mut Self) -> c_int;
+}
+
+macro_rules! impl_matrix_element {
+    (
+        $t:ty, |$v:ident| $is_finite:expr,
+        $add:ident, $sub:ident, $mul:ident, $det:ident, $trans:ident, $hermitian:ident,
+        $eye:ident, $inv:ident, $linsolve:ident, $cgsolve:ident, $chol:ident,
+        $crout:ident, $doolittle:ident, $qr:ident, $gramschmidt:ident,
+    ) => {
+        impl MatrixElement for $t {
+            fn is_finite(self) -> bool {
+                let $v = self;
+                $is_finite
+            }
+
+            unsafe fn matrix_add(
+                x: *mut $t,
+                y: *mut $t,
+                z: *mut $t,
+                r: c_uint,
+                c: c_uint,
+            ) -> c_int {
+                $add(x, y, z, r, c)
+            }
+            unsafe fn matrix_sub(
+                x: *mut $t,
+                y: *mut $t,
+                z: *mut $t,
+                r: c_uint,
+                c: c_uint,
+            ) -> c_int {
+                $sub(x, y, z, r, c)
+            }
+            unsafe fn matrix_mul(
+                x: *mut $t,
+                rx: c_uint,
+                cx: c_uint,
+                y: *mut $t,
+                ry: c_uint,
+                cy: c_uint,
+                z: *mut $t,
+                rz: c_uint,
+                cz: c_uint,
+            ) -> c_int {
+                $mul(x, rx, cx, y, ry, cy, z, rz, cz)
+            }
+            unsafe fn matrix_det(x: *mut $t, r: c_uint, c: c_uint) -> $t {
+                $det(x, r, c)
+            }
+            unsafe fn matrix_trans(x: *mut $t, r: c_uint, c: c_uint) -> c_int {
+                $trans(x, r, c)
+            }
+            unsafe fn matrix_hermitian(x: *mut $t, r: c_uint, c: c_uint) -> c_int {
+                $hermitian(x, r, c)
+            }
+            unsafe fn matrix_eye(x: *mut $t, n: c_uint) -> c_int {
+                $eye(x, n)
+            }
+            unsafe fn matrix_inv(x: *mut $t, r: c_uint, c: c_uint) -> c_int {
+                $inv(x, r, c)
+            }
+            unsafe fn matrix_linsolve(a: *mut $t, n: c_uint, b: *mut $t, x: *mut $t) -> c_int {
+                $linsolve(a, n, b, x, ptr::null_mut())
+            }
+            unsafe fn matrix_cgsolve(a: *mut $t, n: c_uint, b: *mut $t, x: *mut $t) -> c_int {
+                $cgsolve(a, n, b, x, ptr::null_mut())
+            }
+            unsafe fn matrix_chol(a: *mut $t, n: c_uint, l: *mut $t) -> c_int {
+                $chol(a, n, l)
+            }
+            unsafe fn matrix_ludecomp_crout(
+                x: *mut $t,
+                rx: c_uint,
+                cx: c_uint,
+                l: *mut $t,
+                u: *mut $t,
+                p: *mut $t,
+            ) -> c_int {
+                $crout(x, rx, cx, l, u, p)
+            }
+            unsafe fn matrix_ludecomp_doolittle(
+                x: *mut $t,
+                rx: c_uint,
+                cx: c_uint,
+                l: *mut $t,
+                u: *mut $t,
+                p: *mut $t,
+            ) -> c_int {
+                $doolittle(x, rx, cx, l, u, p)
+            }
+            unsafe fn matrix_qrdecomp_gramschmidt(
+                a: *mut $t,
+                m: c_uint,
+                n: c_uint,
+                q: *mut $t,
+                r: *mut $t,
+            ) -> c_int {
+                $qr(a, m, n, q, r)
+            }
+            unsafe fn matrix_gramschmidt(a: *mut $t, r: c_uint, c: c_uint, v: *mut $t) -> c_int {
+                $gramschmidt(a, r, c, v)
+            }
+        }
+    };
+}
+
+impl_matrix_element! {
+    f64, |v| v.is_finite(),
+    matrix_add, matrix_sub, matrix_mul, matrix_det, matrix_trans, matrix_hermitian,
+    matrix_eye, matrix_inv, matrix_linsolve, matrix_cgsolve, matrix_chol,
+    matrix_ludecomp_crout, matrix_ludecomp_doolittle, matrix_qrdecomp_gramschmidt,
+    matrix_gramschmidt,
+}
+
+impl_matrix_element! {
+    f32, |v| v.is_finite(),
+    matrixf_add, matrixf_sub, matrixf_mul, matrixf_det, matrixf_trans, matrixf_hermitian,
+    matrixf_eye, matrixf_inv, matrixf_linsolve, matrixf_cgsolve, matrixf_chol,
+    matrixf_ludecomp_crout, matrixf_ludecomp_doolittle, matrixf_qrdecomp_gramschmidt,
+    matrixf_gramschmidt,
+}
+
+impl_matrix_element! {
+    Complex64, |v| v.re.is_finite() && v.im.is_finite(),
+    matrixc_add, matrixc_sub, matrixc_mul, matrixc_det, matrixc_trans, matrixc_hermitian,
+    matrixc_eye, matrixc_inv, matrixc_linsolve, matrixc_cgsolve, matrixc_chol,
+    matrixc_ludecomp_crout, matrixc_ludecomp_doolittle, matrixc_qrdecomp_gramschmidt,
+    matrixc_gramschmidt,
+}
+
+impl_matrix_element! {
+    Complex32, |v| v.re.is_finite() && v.im.is_finite(),
+    matrixcf_add, matrixcf_sub, matrixcf_mul, matrixcf_det, matrixcf_trans, matrixcf_hermitian,
+    matrixcf_eye, matrixcf_inv, matrixcf_linsolve, matrixcf_cgsolve, matrixcf_chol,
+    matrixcf_ludecomp_crout, matrixcf_ludecomp_doolittle, matrixcf_qrdecomp_gramschmidt,
+    matrixcf_gramschmidt,
+}
+
+/// Dense, row-major matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix<T: MatrixElement> {
+    rows: usize,
+    cols: usize,
+    data: Vec<T>,
+}
+
+/// `l * u == p * a` for the matrix `a` it was computed from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LuDecomposition<T: MatrixElement> {
+    pub l: Matrix<T>,
+    pub u: Matrix<T>,
+    pub p: Matrix<T>,
+}
+
+/// `q * r == a` with `q` orthonormal and `r` upper triangular.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QrDecomposition<T: MatrixElement> {
+    pub q: Matrix<T>,
+    pub r: Matrix<T>,
+}
+
+impl<T: MatrixElement> Matrix<T> {
+    /// Wraps `data`, laid out row by row; fails with [`Error::EIRANGE`] unless
+    /// it holds exactly `rows * cols` elements.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Result<Matrix<T>> {
+        if data.len() != rows * cols {
+            return Err(Error::EIRANGE);
+        }
+        Ok(Matrix { rows, cols, data })
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix {
+            rows,
+            cols,
+            data: vec![T::default(); rows * cols],
+        }
+    }
+
+    pub fn identity(n: usize) -> Matrix<T> {
+        let mut m = Matrix::zeros(n, n);
+        unsafe { T::matrix_eye(m.data.as_mut_ptr(), n as _) };
+        m
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    /// Elements, row by row.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    fn ptr(&self) -> *mut T {
+        self.data.as_ptr() as *mut T
+    }
+
+    fn check_square(&self) -> Result<()> {
+        if self.is_square() {
+            Ok(())
+        } else {
+            Err(Error::EIRANGE)
+        }
+    }
+
+    /// Fails with [`Error::EIVAL`] when a result has blown up, which is how
+    /// liquid's eliminations report singular input.
+    fn check_finite(&self) -> Result<()> {
+        if self.data.iter().all(|&v| v.is_finite()) {
+            Ok(())
+        } else {
+            Err(Error::EIVAL)
+        }
+    }
+
+    pub fn transpose(&self) -> Matrix<T> {
+        let mut m = self.clone();
+        unsafe { T::matrix_trans(m.data.as_mut_ptr(), self.rows as _, self.cols as _) };
+        m.rows = self.cols;
+        m.cols = self.rows;
+        m
+    }
+
+    /// Conjugate transpose; the same as [`transpose`](Self::transpose) for
+    /// real matrices.
+    pub fn hermitian(&self) -> Matrix<T> {
+        let mut m = self.clone();
+        unsafe { T::matrix_hermitian(m.data.as_mut_ptr(), self.rows as _, self.cols as _) };
+        m.rows = self.cols;
+        m.cols = self.rows;
+        m
+    }
+
+    /// Matrix product; fails with [`Error::EIRANGE`] unless `self.cols() ==
+    /// rhs.rows()`.
+    pub fn checked_mul(&self, rhs: &Matrix<T>) -> Result<Matrix<T>> {
+        if self.cols != rhs.rows {
+            return Err(Error::EIRANGE);
+        }
+        let mut z = Matrix::zeros(self.rows, rhs.cols);
+        liquid_try!(unsafe {
+            T::matrix_mul(
+                self.ptr(),
+                self.rows as _,
+                self.cols as _,
+                rhs.ptr(),
+                rhs.rows as _,
+                rhs.cols as _,
+                z.data.as_mut_ptr(),
+                z.rows as _,
+                z.cols as _,
+            )
+        });
+        Ok(z)
+    }
+
+    /// Element-wise sum; fails with [`Error::EIRANGE`] unless the shapes match.
+    pub fn checked_add(&self, rhs: &Matrix<T>) -> Result<Matrix<T>> {
+        if (self.rows, self.cols) != (rhs.rows, rhs.cols) {
+            return Err(Error::EIRANGE);
+        }
+        let mut z = Matrix::zeros(self.rows, self.cols);
+        liquid_try!(unsafe {
+            T::matrix_add(
+                self.ptr(),
+                rhs.ptr(),
+                z.data.as_mut_ptr(),
+                self.rows as _,
+                self.cols as _,
+            )
+        });
+        Ok(z)
+    }
+
+    /// Element-wise difference; fails with [`Error::EIRANGE`] unless the
+    /// shapes match.
+    pub fn checked_sub(&self, rhs: &Matrix<T>) -> Result<Matrix<T>> {
+        if (self.rows, self.cols) != (rhs.rows, rhs.cols) {
+            return Err(Error::EIRANGE);
+        }
+        let mut z = Matrix::zeros(self.rows, self.cols);
+        liquid_try!(unsafe {
+            T::matrix_sub(
+                self.ptr(),
+                rhs.ptr(),
+                z.data.as_mut_ptr(),
+                self.rows as _,
+                self.cols as _,
+            )
+        });
+        Ok(z)
+    }
+
+    /// Determinant; fails with [`Error::EIRANGE`] for a non-square matrix.
+    pub fn det(&self) -> Result<T> {
+        self.check_square()?;
+        Ok(unsafe { T::matrix_det(self.ptr(), self.rows as _, self.cols as _) })
+    }
+
+    /// Inverse; fails with [`Error::EIRANGE`] for a non-square matrix and
+    /// [`Error::EIVAL`] for a singular one.
+    pub fn inv(&self) -> Result<Matrix<T>> {
+        self.check_square()?;
+        let mut m = self.clone();
+        liquid_try!(unsafe { T::matrix_inv(m.data.as_mut_ptr(), self.rows as _, self.cols as _) });
+        m.check_finite()?;
+        Ok(m)
+    }
+
+    /// Solves `self * x == b` by Gauss-Jordan elimination; fails with
+    /// [`Error::EIRANGE`] on mismatched dimensions and [`Error::EIVAL`] for a
+    /// singular matrix.
+    pub fn linsolve(&self, b: &[T]) -> Result<Vec<T>> {
+        self.check_square()?;
+        if b.len() != self.rows {
+            return Err(Error::EIRANGE);
+        }
+        let mut x = vec![T::default(); self.rows];
+        liquid_try!(unsafe {
+            T::matrix_linsolve(
+                self.ptr(),
+                self.rows as _,
+                b.as_ptr() as *mut _,
+                x.as_mut_ptr(),
+            )
+        });
+        if !x.iter().all(|&v| v.is_finite()) {
+            return Err(Error::EIVAL);
+        }
+        Ok(x)
+    }
+
+    /// Solves `self * x == b` by the conjugate gradient method. `self` must be
+    /// symmetric positive definite.
+    pub fn cgsolve(&self, b: &[T]) -> Result<Vec<T>> {
+        self.check_square()?;
+        if b.len() != self.rows {
+            return Err(Error::EIRANGE);
+        }
+        let mut x = vec![T::default(); self.rows];
+        liquid_try!(unsafe {
+            T::matrix_cgsolve(
+                self.ptr(),
+                self.rows as _,
+                b.as_ptr() as *mut _,
+                x.as_mut_ptr(),
+            )
+        });
+        if !x.iter().all(|&v| v.is_finite()) {
+            return Err(Error::ENOCONV);
+        }
+        Ok(x)
+    }
+
+    /// Cholesky factor `l` with `l * l.hermitian() == self`; fails unless
+    /// `self` is Hermitian positive definite.
+    pub fn cholesky(&self) -> Result<Matrix<T>> {
+        self.check_square()?;
+        let mut l = Matrix::zeros(self.rows, self.cols);
+        liquid_try!(unsafe { T::matrix_chol(self.ptr(), self.rows as _, l.data.as_mut_ptr()) });
+        l.check_finite()?;
+        Ok(l)
+    }
+
+    fn lu(
+        &self,
+        decomp: unsafe fn(*mut T, c_uint, c_uint, *mut T, *mut T, *mut T) -> c_int,
+    ) -> Result<LuDecomposition<T>> {
+        self.check_square()?;
+        let n = self.rows;
+        let mut lu = LuDecomposition {
+            l: Matrix::zeros(n, n),
+            u: Matrix::zeros(n, n),
+            p: Matrix::zeros(n, n),
+        };
+        liquid_try!(unsafe {
+            decomp(
+                self.ptr(),
+                n as _,
+                n as _,
+                lu.l.data.as_mut_ptr(),
+                lu.u.data.as_mut_ptr(),
+                lu.p.data.as_mut_ptr(),
+            )
+        });
+        lu.l.check_finite()?;
+        lu.u.check_finite()?;
+        Ok(lu)
+    }
+
+    /// LU decomposition with unit diagonal on `u` (Crout's method).
+    pub fn lu_crout(&self) -> Result<LuDecomposition<T>> {
+        self.lu(T::matrix_ludecomp_crout)
+    }
+
+    /// LU decomposition with unit diagonal on `l` (Doolittle's method).
+    pub fn lu_doolittle(&self) -> Result<LuDecomposition<T>> {
+        self.lu(T::matrix_ludecomp_doolittle)
+    }
+
+    /// QR decomposition by Gram-Schmidt orthogonalization.
+    pub fn qr(&self) -> Result<QrDecomposition<T>> {
+        self.check_square()?;
+        let mut qr = QrDecomposition {
+            q: Matrix::zeros(self.rows, self.cols),
+            r: Matrix::zeros(self.cols, self.cols),
+        };
+        liquid_try!(unsafe {
+            T::matrix_qrdecomp_gramschmidt(
+                self.ptr(),
+                self.rows as _,
+                self.cols as _,
+                qr.q.data.as_mut_ptr(),
+                qr.r.data.as_mut_ptr(),
+            )
+        });
+        qr.q.check_finite()?;
+        qr.r.check_finite()?;
+        Ok(qr)
+    }
+
+    /// Orthonormalizes the columns; fails with [`Error::EIVAL`] if they are
+    /// linearly dependent.
+    pub fn gram_schmidt(&self) -> Result<Matrix<T>> {
+        let mut v = Matrix::zeros(self.rows, self.cols);
+        liquid_try!(unsafe {
+            T::matrix_gramschmidt(
+                self.ptr(),
+                self.rows as _,
+                self.cols as _,
+                v.data.as_mut_ptr(),
+            )
+        });
+        v.check_finite()?;
+        Ok(v)
+    }
+}
+
+impl<T: MatrixElement> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (r, c): (usize, usize)) -> &T {
+        assert!(r < self.rows && c < self.cols);
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl<T: MatrixElement> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut T {
+        assert!(r < self.rows && c < self.cols);
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+macro_rules! impl_matrix_op {
+    ($op:ident, $method:ident, $checked:ident) => {
+        impl<'a, 'b, T: MatrixElement> $op<&'b Matrix<T>> for &'a Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, rhs: &'b Matrix<T>) -> Matrix<T> {
+                match self.$checked(rhs) {
+                    Ok(m) => m,
+                    Err(_) => panic!(
+                        "matrix dimension mismatch: {}x{} {} {}x{}",
+                        self.rows,
+                        self.cols,
+                        stringify!($method),
+                        rhs.rows,
+                        rhs.cols
+                    ),
+                }
+            }
+        }
+
+        impl<T: MatrixElement> $op<Matrix<T>> for Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, rhs: Matrix<T>) -> Matrix<T> {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_matrix_op!(Add, add, checked_add);
+impl_matrix_op!(Sub, sub, checked_sub);
+impl_matrix_op!(Mul, mul, checked_mul);
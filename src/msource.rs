@@ -0,0 +1,217 @@
+//! Multi-signal source generator (`msourcecf`).
+
+use liquidizers_sys::*;
+
+use std::collections::HashMap;
+use std::os::raw::{c_int, c_uint, c_void};
+use std::slice;
+
+use crate::{created, Complex32, Error, ModulationScheme, Result};
+
+/// Identifies a signal added to a [`MultiSource`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct SourceId(c_int);
+
+type UserCallback = Box<dyn FnMut(&mut [Complex32])>;
+
+unsafe extern "C" fn msource_trampoline(
+    userdata: *mut c_void,
+    v: *mut liquid_float_complex,
+    n: c_uint,
+) -> c_int {
+    let callback = &mut *(userdata as *mut UserCallback);
+    if !v.is_null() {
+        callback(slice::from_raw_parts_mut(v, n as usize));
+    }
+    0
+}
+
+/// Sum of independently configurable signals, each mixed to its own carrier
+/// (`msourcecf`). Useful for building receiver test vectors.
+pub struct MultiSource {
+    q: msourcecf,
+    users: HashMap<c_int, Box<UserCallback>>,
+}
+
+impl MultiSource {
+    /// Creates a source with `m` synthesis channels, prototype filter
+    /// semi-length `filter_len` and stop-band attenuation `attenuation` dB.
+    pub fn create(m: u32, filter_len: u32, attenuation: f32) -> Result<MultiSource> {
+        let q = created(unsafe { msourcecf_create(m, filter_len, attenuation) })?;
+        Ok(MultiSource {
+            q,
+            users: HashMap::new(),
+        })
+    }
+
+    pub fn create_default() -> Result<MultiSource> {
+        let q = created(unsafe { msourcecf_create_default() })?;
+        Ok(MultiSource {
+            q,
+            users: HashMap::new(),
+        })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { msourcecf_reset(self.q) });
+        Ok(())
+    }
+
+    fn added(id: c_int) -> Result<SourceId> {
+        if id < 0 {
+            Err(Error::EICONFIG)
+        } else {
+            Ok(SourceId(id))
+        }
+    }
+
+    /// Adds a tone at normalized frequency `fc`.
+    pub fn add_tone(&mut self, fc: f32, bw: f32, gain: f32) -> Result<SourceId> {
+        MultiSource::added(unsafe { msourcecf_add_tone(self.q, fc, bw, gain) })
+    }
+
+    /// Adds a chirp sweeping `bw` over `duration` samples.
+    pub fn add_chirp(
+        &mut self,
+        fc: f32,
+        bw: f32,
+        gain: f32,
+        duration: f32,
+        negate: bool,
+        repeat: bool,
+    ) -> Result<SourceId> {
+        MultiSource::added(unsafe {
+            msourcecf_add_chirp(self.q, fc, bw, gain, duration, negate as _, repeat as _)
+        })
+    }
+
+    /// Adds band-limited noise.
+    pub fn add_noise(&mut self, fc: f32, bw: f32, gain: f32) -> Result<SourceId> {
+        MultiSource::added(unsafe { msourcecf_add_noise(self.q, fc, bw, gain) })
+    }
+
+    /// Adds random symbols of `ms`, pulse-shaped with a filter of delay `m`
+    /// symbols and excess bandwidth `beta`.
+    pub fn add_modem(
+        &mut self,
+        fc: f32,
+        bw: f32,
+        gain: f32,
+        ms: ModulationScheme,
+        m: u32,
+        beta: f32,
+    ) -> Result<SourceId> {
+        MultiSource::added(unsafe {
+            msourcecf_add_modem(self.q, fc, bw, gain, ms.to_raw() as _, m, beta)
+        })
+    }
+
+    /// Adds an FSK signal with `m` bits/symbol and `k` samples/symbol.
+    pub fn add_fsk(&mut self, fc: f32, bw: f32, gain: f32, m: u32, k: u32) -> Result<SourceId> {
+        MultiSource::added(unsafe { msourcecf_add_fsk(self.q, fc, bw, gain, m, k) })
+    }
+
+    /// Adds a GMSK signal with filter delay `m` symbols and bandwidth-time product `bt`.
+    pub fn add_gmsk(&mut self, fc: f32, bw: f32, gain: f32, m: u32, bt: f32) -> Result<SourceId> {
+        MultiSource::added(unsafe { msourcecf_add_gmsk(self.q, fc, bw, gain, m, bt) })
+    }
+
+    /// Adds a signal generated by `callback`, which fills each block of baseband
+    /// samples before it is mixed up to `fc`.
+    pub fn add_user<F>(&mut self, fc: f32, bw: f32, gain: f32, callback: F) -> Result<SourceId>
+    where
+        F: FnMut(&mut [Complex32]) + 'static,
+    {
+        let mut callback: Box<UserCallback> = Box::new(Box::new(callback));
+        let userdata = &mut *callback as *mut UserCallback as *mut c_void;
+        let id = MultiSource::added(unsafe {
+            msourcecf_add_user(self.q, fc, bw, gain, userdata, Some(msource_trampoline))
+        })?;
+        self.users.insert(id.0, callback);
+        Ok(id)
+    }
+
+    /// Removes a source; its handle becomes invalid.
+    pub fn remove(&mut self, id: SourceId) -> Result<()> {
+        liquid_try!(unsafe { msourcecf_remove(self.q, id.0) });
+        self.users.remove(&id.0);
+        Ok(())
+    }
+
+    /// Borrows a handle for adjusting source `id`.
+    pub fn source(&mut self, id: SourceId) -> Source<'_> {
+        Source { msource: self, id }
+    }
+
+    /// Total number of samples generated so far.
+    pub fn num_samples(&self) -> u64 {
+        unsafe { msourcecf_get_num_samples(self.q) as u64 }
+    }
+
+    /// Fills `buf` with the sum of all enabled sources.
+    pub fn write_samples(&mut self, buf: &mut [Complex32]) -> Result<()> {
+        liquid_try!(unsafe { msourcecf_write_samples(self.q, buf.as_mut_ptr(), buf.len() as _) });
+        Ok(())
+    }
+}
+
+impl Drop for MultiSource {
+    fn drop(&mut self) {
+        unsafe { msourcecf_destroy(self.q) };
+    }
+}
+
+/// Handle to one signal of a [`MultiSource`].
+pub struct Source<'a> {
+    msource: &'a mut MultiSource,
+    id: SourceId,
+}
+
+impl<'a> Source<'a> {
+    pub fn id(&self) -> SourceId {
+        self.id
+    }
+
+    pub fn enable(&mut self) -> Result<()> {
+        liquid_try!(unsafe { msourcecf_enable(self.msource.q, self.id.0) });
+        Ok(())
+    }
+
+    pub fn disable(&mut self) -> Result<()> {
+        liquid_try!(unsafe { msourcecf_disable(self.msource.q, self.id.0) });
+        Ok(())
+    }
+
+    /// Gain in dB.
+    pub fn gain(&self) -> Result<f32> {
+        let mut gain = 0.0;
+        liquid_try!(unsafe { msourcecf_get_gain(self.msource.q, self.id.0, &mut gain) });
+        Ok(gain)
+    }
+
+    pub fn set_gain(&mut self, gain: f32) -> Result<()> {
+        liquid_try!(unsafe { msourcecf_set_gain(self.msource.q, self.id.0, gain) });
+        Ok(())
+    }
+
+    /// Carrier frequency, in radians/sample.
+    pub fn frequency(&self) -> Result<f32> {
+        let mut dphi = 0.0;
+        liquid_try!(unsafe { msourcecf_get_frequency(self.msource.q, self.id.0, &mut dphi) });
+        Ok(dphi)
+    }
+
+    pub fn set_frequency(&mut self, dphi: f32) -> Result<()> {
+        liquid_try!(unsafe { msourcecf_set_frequency(self.msource.q, self.id.0, dphi) });
+        Ok(())
+    }
+
+    /// Number of samples this source has generated.
+    // `c_ulong` is only 32 bits wide on Windows.
+    #[allow(clippy::useless_conversion)]
+    pub fn num_samples(&self) -> Result<u64> {
+        let mut n: std::os::raw::c_ulong = 0;
+        liquid_try!(unsafe { msourcecf_get_num_samples_source(self.msource.q, self.id.0, &mut n) });
+        Ok(n.into())
+    }
+}
@@ -0,0 +1,539 @@
+//! Numerical optimizers (`gradsearch`, `qnsearch`, `qs1dsearch`, `gasearch`)
+//! driven by Rust closures.
+
+use liquidizers_sys::*;
+
+use std::mem::ManuallyDrop;
+use std::os::raw::{c_uint, c_void};
+use std::slice;
+
+use crate::{created, Error, Result};
+
+liquid_enum! {
+    /// Whether an optimizer seeks the minimum or maximum of its utility.
+    pub enum OptimDirection: u32 {
+        Minimize = LIQUID_OPTIM_MINIMIZE,
+        Maximize = LIQUID_OPTIM_MAXIMIZE,
+    }
+}
+
+/// Best point found by an [`Optimizer`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Optimum {
+    pub v: Vec<f32>,
+    pub utility: f32,
+}
+
+/// Common interface of the search algorithms.
+pub trait Optimizer {
+    /// Searches for at most `max_iterations` iterations, stopping early once
+    /// the utility reaches `target`.
+    fn run(&mut self, max_iterations: u32, target: f32) -> Optimum;
+}
+
+type Utility = Box<dyn FnMut(&[f32]) -> f32>;
+
+unsafe extern "C" fn utility_trampoline(userdata: *mut c_void, v: *mut f32, n: c_uint) -> f32 {
+    let utility = &mut *(userdata as *mut Utility);
+    utility(slice::from_raw_parts(v, n as usize))
+}
+
+unsafe extern "C" fn utility_1d_trampoline(v: f32, userdata: *mut c_void) -> f32 {
+    let utility = &mut *(userdata as *mut Utility);
+    utility(&[v])
+}
+
+fn boxed_utility<F>(utility: F) -> (Box<Utility>, *mut c_void)
+where
+    F: FnMut(&[f32]) -> f32 + 'static,
+{
+    let mut utility: Box<Utility> = Box::new(Box::new(utility));
+    let userdata = &mut *utility as *mut Utility as *mut c_void;
+    (utility, userdata)
+}
+
+/// Gradient search (`gradsearch`).
+pub struct GradSearch {
+    q: gradsearch,
+    v: Box<[f32]>,
+    _utility: Box<Utility>,
+}
+
+impl GradSearch {
+    /// Creates a search starting from `v0`.
+    pub fn create<F>(v0: &[f32], utility: F, direction: OptimDirection) -> Result<GradSearch>
+    where
+        F: FnMut(&[f32]) -> f32 + 'static,
+    {
+        let (utility, userdata) = boxed_utility(utility);
+        let mut v: Box<[f32]> = v0.into();
+        let q = created(unsafe {
+            gradsearch_create(
+                userdata,
+                v.as_mut_ptr(),
+                v.len() as _,
+                Some(utility_trampoline),
+                direction.to_raw() as _,
+            )
+        })?;
+        Ok(GradSearch {
+            q,
+            v,
+            _utility: utility,
+        })
+    }
+
+    /// Takes one step and returns the new utility.
+    pub fn step(&mut self) -> f32 {
+        unsafe { gradsearch_step(self.q) }
+    }
+
+    /// Current parameters.
+    pub fn v(&self) -> &[f32] {
+        &self.v
+    }
+}
+
+impl Optimizer for GradSearch {
+    fn run(&mut self, max_iterations: u32, target: f32) -> Optimum {
+        let utility = unsafe { gradsearch_execute(self.q, max_iterations, target) };
+        Optimum {
+            v: self.v.to_vec(),
+            utility,
+        }
+    }
+}
+
+impl Drop for GradSearch {
+    fn drop(&mut self) {
+        unsafe { gradsearch_destroy(self.q) };
+    }
+}
+
+/// Quasi-Newton search (`qnsearch`).
+pub struct QnSearch {
+    q: qnsearch,
+    v: Box<[f32]>,
+    _utility: Box<Utility>,
+}
+
+impl QnSearch {
+    /// Creates a search starting from `v0`.
+    pub fn create<F>(v0: &[f32], utility: F, direction: OptimDirection) -> Result<QnSearch>
+    where
+        F: FnMut(&[f32]) -> f32 + 'static,
+    {
+        let (utility, userdata) = boxed_utility(utility);
+        let mut v: Box<[f32]> = v0.into();
+        let q = created(unsafe {
+            qnsearch_create(
+                userdata,
+                v.as_mut_ptr(),
+                v.len() as _,
+                Some(utility_trampoline),
+                direction.to_raw() as _,
+            )
+        })?;
+        Ok(QnSearch {
+            q,
+            v,
+            _utility: utility,
+        })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qnsearch_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn step(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qnsearch_step(self.q) });
+        Ok(())
+    }
+
+    /// Current parameters.
+    pub fn v(&self) -> &[f32] {
+        &self.v
+    }
+}
+
+impl Optimizer for QnSearch {
+    fn run(&mut self, max_iterations: u32, target: f32) -> Optimum {
+        let utility = unsafe { qnsearch_execute(self.q, max_iterations, target) };
+        Optimum {
+            v: self.v.to_vec(),
+            utility,
+        }
+    }
+}
+
+impl Drop for QnSearch {
+    fn drop(&mut self) {
+        unsafe { qnsearch_destroy(self.q) };
+    }
+}
+
+/// One-dimensional quadratic search (`qs1dsearch`). The utility receives a
+/// single-element slice.
+pub struct Qs1dSearch {
+    q: qs1dsearch,
+    direction: OptimDirection,
+    _utility: Box<Utility>,
+}
+
+impl Qs1dSearch {
+    pub fn create<F>(utility: F, direction: OptimDirection) -> Result<Qs1dSearch>
+    where
+        F: FnMut(&[f32]) -> f32 + 'static,
+    {
+        let (utility, userdata) = boxed_utility(utility);
+        let q = created(unsafe {
+            qs1dsearch_create(
+                Some(utility_1d_trampoline),
+                userdata,
+                direction.to_raw() as _,
+            )
+        })?;
+        Ok(Qs1dSearch {
+            q,
+            direction,
+            _utility: utility,
+        })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qs1dsearch_reset(self.q) });
+        Ok(())
+    }
+
+    /// Starts the search around `v0`, letting liquid find the initial bounds.
+    pub fn init(&mut self, v0: f32) -> Result<()> {
+        liquid_try!(unsafe { qs1dsearch_init(self.q, v0) });
+        Ok(())
+    }
+
+    /// Starts the search at `v_init`, stepping by `step` to find the bounds.
+    pub fn init_direction(&mut self, v_init: f32, step: f32) -> Result<()> {
+        liquid_try!(unsafe { qs1dsearch_init_direction(self.q, v_init, step) });
+        Ok(())
+    }
+
+    /// Starts the search between the bounds `vn` and `vp`.
+    pub fn init_bounds(&mut self, vn: f32, vp: f32) -> Result<()> {
+        liquid_try!(unsafe { qs1dsearch_init_bounds(self.q, vn, vp) });
+        Ok(())
+    }
+
+    pub fn step(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qs1dsearch_step(self.q) });
+        Ok(())
+    }
+
+    /// Runs the search to convergence.
+    pub fn execute(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qs1dsearch_execute(self.q) });
+        Ok(())
+    }
+
+    pub fn num_steps(&self) -> u32 {
+        unsafe { qs1dsearch_get_num_steps(self.q) }
+    }
+
+    /// Best parameter found so far.
+    pub fn opt_v(&self) -> f32 {
+        unsafe { qs1dsearch_get_opt_v(self.q) }
+    }
+
+    /// Utility at [`opt_v`](Self::opt_v).
+    pub fn opt_u(&self) -> f32 {
+        unsafe { qs1dsearch_get_opt_u(self.q) }
+    }
+
+    fn reached(&self, target: f32) -> bool {
+        match self.direction {
+            OptimDirection::Minimize => self.opt_u() <= target,
+            OptimDirection::Maximize => self.opt_u() >= target,
+        }
+    }
+}
+
+impl Optimizer for Qs1dSearch {
+    /// Steps until `max_iterations` steps have run, the utility reaches
+    /// `target` or the search stops converging. Call one of the `init`
+    /// methods first.
+    fn run(&mut self, max_iterations: u32, target: f32) -> Optimum {
+        for _ in 0..max_iterations {
+            if self.reached(target) || self.step().is_err() {
+                break;
+            }
+        }
+        Optimum {
+            v: vec![self.opt_v()],
+            utility: self.opt_u(),
+        }
+    }
+}
+
+impl Drop for Qs1dSearch {
+    fn drop(&mut self) {
+        unsafe { qs1dsearch_destroy(self.q) };
+    }
+}
+
+/// Bit-string genome for [`GaSearch`], made of traits of configurable width
+/// (`chromosome`).
+pub struct Chromosome {
+    c: chromosome,
+}
+
+unsafe impl Send for Chromosome {}
+
+impl Chromosome {
+    /// Creates a chromosome with one trait per entry of `bits_per_trait`.
+    pub fn create(bits_per_trait: &[u32]) -> Result<Chromosome> {
+        let c = created(unsafe {
+            chromosome_create(bits_per_trait.as_ptr() as *mut _, bits_per_trait.len() as _)
+        })?;
+        Ok(Chromosome { c })
+    }
+
+    /// Creates a chromosome of `num_traits` traits, each `bits_per_trait` wide.
+    pub fn create_basic(num_traits: u32, bits_per_trait: u32) -> Result<Chromosome> {
+        let c = created(unsafe { chromosome_create_basic(num_traits, bits_per_trait) })?;
+        Ok(Chromosome { c })
+    }
+
+    pub fn num_traits(&self) -> usize {
+        unsafe { chromosome_get_num_traits(self.c) as usize }
+    }
+
+    fn check_traits(&self, n: usize) -> Result<()> {
+        if n == self.num_traits() {
+            Ok(())
+        } else {
+            Err(Error::EIRANGE)
+        }
+    }
+
+    /// Zeroes every trait.
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { chromosome_reset(self.c) });
+        Ok(())
+    }
+
+    /// Sets the raw trait values; fails with [`Error::EIRANGE`] unless `v` has
+    /// `num_traits()` entries.
+    pub fn init(&mut self, v: &[u32]) -> Result<()> {
+        self.check_traits(v.len())?;
+        liquid_try!(unsafe { chromosome_init(self.c, v.as_ptr() as *mut _) });
+        Ok(())
+    }
+
+    /// Sets the traits from values in `[0, 1]`; fails with [`Error::EIRANGE`]
+    /// unless `v` has `num_traits()` entries.
+    pub fn initf(&mut self, v: &[f32]) -> Result<()> {
+        self.check_traits(v.len())?;
+        liquid_try!(unsafe { chromosome_initf(self.c, v.as_ptr() as *mut _) });
+        Ok(())
+    }
+
+    pub fn init_random(&mut self) -> Result<()> {
+        liquid_try!(unsafe { chromosome_init_random(self.c) });
+        Ok(())
+    }
+
+    /// Flips bit `index` of the genome.
+    pub fn mutate(&mut self, index: u32) -> Result<()> {
+        liquid_try!(unsafe { chromosome_mutate(self.c, index) });
+        Ok(())
+    }
+
+    /// Overwrites `self` with the first `threshold` bits of `p1` followed by
+    /// the rest of `p2`.
+    pub fn crossover(&mut self, p1: &Chromosome, p2: &Chromosome, threshold: u32) -> Result<()> {
+        liquid_try!(unsafe { chromosome_crossover(p1.c, p2.c, self.c, threshold) });
+        Ok(())
+    }
+
+    /// Raw value of trait `index`.
+    pub fn value(&self, index: usize) -> Result<u32> {
+        if index >= self.num_traits() {
+            return Err(Error::EIRANGE);
+        }
+        Ok(unsafe { chromosome_value(self.c, index as _) })
+    }
+
+    /// Value of trait `index` scaled to `[0, 1]`.
+    pub fn valuef(&self, index: usize) -> Result<f32> {
+        if index >= self.num_traits() {
+            return Err(Error::EIRANGE);
+        }
+        Ok(unsafe { chromosome_valuef(self.c, index as _) })
+    }
+
+    /// All traits scaled to `[0, 1]`.
+    pub fn valuesf(&self) -> Vec<f32> {
+        (0..self.num_traits())
+            .map(|i| unsafe { chromosome_valuef(self.c, i as _) })
+            .collect()
+    }
+}
+
+impl Clone for Chromosome {
+    fn clone(&self) -> Self {
+        Chromosome {
+            c: unsafe { chromosome_create_clone(self.c) },
+        }
+    }
+}
+
+impl Drop for Chromosome {
+    fn drop(&mut self) {
+        unsafe { chromosome_destroy(self.c) };
+    }
+}
+
+type GaUtility = Box<dyn FnMut(&Chromosome) -> f32>;
+
+unsafe extern "C" fn gasearch_trampoline(userdata: *mut c_void, c: chromosome) -> f32 {
+    let utility = &mut *(userdata as *mut GaUtility);
+    // liquid owns `c`; borrow it without destroying it afterwards.
+    let c = ManuallyDrop::new(Chromosome { c });
+    utility(&c)
+}
+
+/// Genetic algorithm search over [`Chromosome`]s (`gasearch`).
+pub struct GaSearch {
+    q: gasearch,
+    parent: Chromosome,
+    _utility: Box<GaUtility>,
+}
+
+impl GaSearch {
+    /// Creates a search whose population is modelled on `parent`.
+    pub fn create<F>(utility: F, parent: Chromosome, direction: OptimDirection) -> Result<GaSearch>
+    where
+        F: FnMut(&Chromosome) -> f32 + 'static,
+    {
+        let mut utility: Box<GaUtility> = Box::new(Box::new(utility));
+        let userdata = &mut *utility as *mut GaUtility as *mut c_void;
+        let q = created(unsafe {
+            gasearch_create(
+                Some(gasearch_trampoline),
+                userdata,
+                parent.c,
+                direction.to_raw() as _,
+            )
+        })?;
+        Ok(GaSearch {
+            q,
+            parent,
+            _utility: utility,
+        })
+    }
+
+    /// Like [`create`](Self::create), with an explicit population size and
+    /// mutation rate.
+    pub fn create_advanced<F>(
+        utility: F,
+        parent: Chromosome,
+        direction: OptimDirection,
+        population_size: u32,
+        mutation_rate: f32,
+    ) -> Result<GaSearch>
+    where
+        F: FnMut(&Chromosome) -> f32 + 'static,
+    {
+        let mut utility: Box<GaUtility> = Box::new(Box::new(utility));
+        let userdata = &mut *utility as *mut GaUtility as *mut c_void;
+        let q = created(unsafe {
+            gasearch_create_advanced(
+                Some(gasearch_trampoline),
+                userdata,
+                parent.c,
+                direction.to_raw() as _,
+                population_size,
+                mutation_rate,
+            )
+        })?;
+        Ok(GaSearch {
+            q,
+            parent,
+            _utility: utility,
+        })
+    }
+
+    pub fn set_mutation_rate(&mut self, mutation_rate: f32) -> Result<()> {
+        liquid_try!(unsafe { gasearch_set_mutation_rate(self.q, mutation_rate) });
+        Ok(())
+    }
+
+    /// Sets the population size and how many of the fittest survive each
+    /// generation.
+    pub fn set_population_size(&mut self, population_size: u32, selection_size: u32) -> Result<()> {
+        liquid_try!(unsafe {
+            gasearch_set_population_size(self.q, population_size, selection_size)
+        });
+        Ok(())
+    }
+
+    /// Advances one generation.
+    pub fn evolve(&mut self) -> Result<()> {
+        liquid_try!(unsafe { gasearch_evolve(self.q) });
+        Ok(())
+    }
+
+    /// Fittest chromosome so far and its utility.
+    pub fn best(&self) -> Result<(Chromosome, f32)> {
+        let c = self.parent.clone();
+        let mut utility = 0.0;
+        liquid_try!(unsafe { gasearch_getopt(self.q, c.c, &mut utility) });
+        Ok((c, utility))
+    }
+}
+
+impl Optimizer for GaSearch {
+    /// The optimum's parameters are the fittest chromosome's traits, scaled to
+    /// `[0, 1]`.
+    fn run(&mut self, max_iterations: u32, target: f32) -> Optimum {
+        let utility = unsafe { gasearch_run(self.q, max_iterations, target) };
+        let v = match self.best() {
+            Ok((c, _)) => c.valuesf(),
+            Err(_) => Vec::new(),
+        };
+        Optimum { v, utility }
+    }
+}
+
+impl Drop for GaSearch {
+    fn drop(&mut self) {
+        unsafe { gasearch_destroy(self.q) };
+    }
+}
+
+/// liquid's standard utility functions for exercising the optimizers.
+pub mod test_functions {
+    use liquidizers_sys::*;
+
+    use std::ptr;
+
+    /// Rosenbrock's banana function; minimum 0 at `[1, 1, ...]`.
+    pub fn rosenbrock(v: &[f32]) -> f32 {
+        unsafe { liquid_rosenbrock(ptr::null_mut(), v.as_ptr() as *mut _, v.len() as _) }
+    }
+
+    /// Inverted Gaussian; minimum -1 at the origin.
+    pub fn invgauss(v: &[f32]) -> f32 {
+        unsafe { liquid_invgauss(ptr::null_mut(), v.as_ptr() as *mut _, v.len() as _) }
+    }
+
+    /// Function with many local maxima.
+    pub fn multimodal(v: &[f32]) -> f32 {
+        unsafe { liquid_multimodal(ptr::null_mut(), v.as_ptr() as *mut _, v.len() as _) }
+    }
+
+    /// Spiral-shaped valley; two-dimensional.
+    pub fn spiral(v: &[f32]) -> f32 {
+        unsafe { liquid_spiral(ptr::null_mut(), v.as_ptr() as *mut _, v.len() as _) }
+    }
+}
@@ -0,0 +1,79 @@
+//! Order-statistic filter (`ordfilt_rrrf`).
+
+use liquidizers_sys::*;
+
+use crate::{created, Result};
+
+/// Outputs the `k`-th smallest of the last `n` samples (`ordfilt_rrrf`).
+pub struct OrderFilter {
+    q: ordfilt_rrrf,
+}
+
+unsafe impl Send for OrderFilter {}
+
+impl OrderFilter {
+    /// Creates a filter over a window of `n` samples returning the `k`-th
+    /// smallest, counting from zero.
+    pub fn create(n: u32, k: u32) -> Result<OrderFilter> {
+        let q = created(unsafe { ordfilt_rrrf_create(n, k) })?;
+        Ok(OrderFilter { q })
+    }
+
+    /// Creates a median filter over a window of `2*m+1` samples.
+    pub fn create_medfilt(m: u32) -> Result<OrderFilter> {
+        let q = created(unsafe { ordfilt_rrrf_create_medfilt(m) })?;
+        Ok(OrderFilter { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { ordfilt_rrrf_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn push(&mut self, x: f32) -> Result<()> {
+        liquid_try!(unsafe { ordfilt_rrrf_push(self.q, x) });
+        Ok(())
+    }
+
+    pub fn write(&mut self, x: &[f32]) -> Result<()> {
+        liquid_try!(unsafe { ordfilt_rrrf_write(self.q, x.as_ptr() as *mut _, x.len() as _) });
+        Ok(())
+    }
+
+    /// Computes the output over the current window.
+    pub fn execute(&mut self) -> Result<f32> {
+        let mut y = 0.0;
+        liquid_try!(unsafe { ordfilt_rrrf_execute(self.q, &mut y) });
+        Ok(y)
+    }
+
+    /// Pushes `x` and computes the output.
+    pub fn execute_one(&mut self, x: f32) -> Result<f32> {
+        let mut y = 0.0;
+        liquid_try!(unsafe { ordfilt_rrrf_execute_one(self.q, x, &mut y) });
+        Ok(y)
+    }
+
+    /// Filters a block of samples.
+    pub fn execute_block(&mut self, x: &[f32]) -> Result<Vec<f32>> {
+        let mut y = vec![0.0; x.len()];
+        liquid_try!(unsafe {
+            ordfilt_rrrf_execute_block(self.q, x.as_ptr() as *mut _, x.len() as _, y.as_mut_ptr())
+        });
+        Ok(y)
+    }
+}
+
+impl Clone for OrderFilter {
+    fn clone(&self) -> Self {
+        OrderFilter {
+            q: unsafe { ordfilt_rrrf_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for OrderFilter {
+    fn drop(&mut self) {
+        unsafe { ordfilt_rrrf_destroy(self.q) };
+    }
+}
@@ -0,0 +1,314 @@
+//! Packet modem (`qpacketmodem`) and pilot insertion (`qpilotgen`/`qpilotsync`).
+
+use liquidizers_sys::*;
+
+use std::result;
+
+use crate::{
+    check_len, created, Complex32, CrcError, CrcScheme, FecScheme, ModulationScheme, Result,
+};
+
+/// Encodes a payload with CRC and FEC and maps it onto modem symbols (`qpacketmodem`).
+pub struct PacketModem {
+    q: qpacketmodem,
+}
+
+unsafe impl Send for PacketModem {}
+
+impl PacketModem {
+    pub fn create() -> Result<PacketModem> {
+        let q = created(unsafe { qpacketmodem_create() })?;
+        Ok(PacketModem { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qpacketmodem_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn configure(
+        &mut self,
+        payload_len: usize,
+        crc: CrcScheme,
+        fec0: FecScheme,
+        fec1: FecScheme,
+        modscheme: ModulationScheme,
+    ) -> Result<()> {
+        liquid_try!(unsafe {
+            qpacketmodem_configure(
+                self.q,
+                payload_len as _,
+                crc.to_raw(),
+                fec0.to_raw(),
+                fec1.to_raw(),
+                modscheme.to_raw() as _,
+            )
+        });
+        Ok(())
+    }
+
+    /// Number of modulated symbols in an encoded frame.
+    pub fn frame_len(&self) -> usize {
+        unsafe { qpacketmodem_get_frame_len(self.q) as usize }
+    }
+
+    pub fn payload_len(&self) -> usize {
+        unsafe { qpacketmodem_get_payload_len(self.q) as usize }
+    }
+
+    pub fn crc(&self) -> Option<CrcScheme> {
+        CrcScheme::from_raw(unsafe { qpacketmodem_get_crc(self.q) })
+    }
+
+    pub fn fec0(&self) -> Option<FecScheme> {
+        FecScheme::from_raw(unsafe { qpacketmodem_get_fec0(self.q) })
+    }
+
+    pub fn fec1(&self) -> Option<FecScheme> {
+        FecScheme::from_raw(unsafe { qpacketmodem_get_fec1(self.q) })
+    }
+
+    pub fn modscheme(&self) -> Option<ModulationScheme> {
+        ModulationScheme::from_raw(unsafe { qpacketmodem_get_modscheme(self.q) })
+    }
+
+    /// Phase error of the last demodulated frame.
+    pub fn demodulator_phase_error(&self) -> f32 {
+        unsafe { qpacketmodem_get_demodulator_phase_error(self.q) }
+    }
+
+    /// Error vector magnitude of the last demodulated frame.
+    pub fn demodulator_evm(&self) -> f32 {
+        unsafe { qpacketmodem_get_demodulator_evm(self.q) }
+    }
+
+    /// Encodes `payload` into `frame_len()` modulated samples; fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless `payload` is
+    /// `payload_len()` bytes.
+    pub fn encode(&mut self, payload: &[u8]) -> Result<Vec<Complex32>> {
+        check_len(payload.len(), self.payload_len())?;
+        let mut frame = vec![Complex32::default(); self.frame_len()];
+        liquid_try!(unsafe { qpacketmodem_encode(self.q, payload.as_ptr(), frame.as_mut_ptr()) });
+        Ok(frame)
+    }
+
+    /// Encodes `payload` into `frame_len()` symbol indices; fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless `payload` is
+    /// `payload_len()` bytes.
+    pub fn encode_syms(&mut self, payload: &[u8]) -> Result<Vec<u8>> {
+        check_len(payload.len(), self.payload_len())?;
+        let mut syms = vec![0; self.frame_len()];
+        liquid_try!(unsafe {
+            qpacketmodem_encode_syms(self.q, payload.as_ptr(), syms.as_mut_ptr())
+        });
+        Ok(syms)
+    }
+
+    fn decoded(&self, payload: Vec<u8>, crc_pass: i32) -> result::Result<Vec<u8>, CrcError> {
+        if crc_pass != 0 {
+            Ok(payload)
+        } else {
+            Err(CrcError { payload })
+        }
+    }
+
+    /// Demodulates and decodes a frame using hard decisions. Fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless `frame` is
+    /// `frame_len()` samples; the inner result carries the CRC outcome.
+    pub fn decode(&mut self, frame: &[Complex32]) -> Result<result::Result<Vec<u8>, CrcError>> {
+        check_len(frame.len(), self.frame_len())?;
+        let mut payload = vec![0; self.payload_len()];
+        let rc =
+            unsafe { qpacketmodem_decode(self.q, frame.as_ptr() as *mut _, payload.as_mut_ptr()) };
+        Ok(self.decoded(payload, rc))
+    }
+
+    /// Demodulates and decodes a frame using soft decisions. Fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless `frame` is
+    /// `frame_len()` samples; the inner result carries the CRC outcome.
+    pub fn decode_soft(
+        &mut self,
+        frame: &[Complex32],
+    ) -> Result<result::Result<Vec<u8>, CrcError>> {
+        check_len(frame.len(), self.frame_len())?;
+        let mut payload = vec![0; self.payload_len()];
+        let rc = unsafe {
+            qpacketmodem_decode_soft(self.q, frame.as_ptr() as *mut _, payload.as_mut_ptr())
+        };
+        Ok(self.decoded(payload, rc))
+    }
+
+    /// Decodes a frame from symbol indices. Fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless `syms` is
+    /// `frame_len()` symbols; the inner result carries the CRC outcome.
+    pub fn decode_syms(&mut self, syms: &[u8]) -> Result<result::Result<Vec<u8>, CrcError>> {
+        check_len(syms.len(), self.frame_len())?;
+        let mut payload = vec![0; self.payload_len()];
+        let rc = unsafe {
+            qpacketmodem_decode_syms(self.q, syms.as_ptr() as *mut _, payload.as_mut_ptr())
+        };
+        Ok(self.decoded(payload, rc))
+    }
+
+    /// Feeds one received sample into the soft decoder; call
+    /// [`decode_soft_payload`](Self::decode_soft_payload) after `frame_len()` samples.
+    pub fn decode_soft_sym(&mut self, sym: Complex32) -> Result<()> {
+        liquid_try!(unsafe { qpacketmodem_decode_soft_sym(self.q, sym) });
+        Ok(())
+    }
+
+    /// Decodes the samples accumulated with [`decode_soft_sym`](Self::decode_soft_sym).
+    pub fn decode_soft_payload(&mut self) -> result::Result<Vec<u8>, CrcError> {
+        let mut payload = vec![0; self.payload_len()];
+        let rc = unsafe { qpacketmodem_decode_soft_payload(self.q, payload.as_mut_ptr()) };
+        self.decoded(payload, rc)
+    }
+}
+
+impl Clone for PacketModem {
+    fn clone(&self) -> Self {
+        PacketModem {
+            q: unsafe { qpacketmodem_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for PacketModem {
+    fn drop(&mut self) {
+        unsafe { qpacketmodem_destroy(self.q) };
+    }
+}
+
+/// Inserts QPSK pilots into a block of payload symbols (`qpilotgen`).
+pub struct PilotFrameGen {
+    q: qpilotgen,
+    payload_len: usize,
+}
+
+unsafe impl Send for PilotFrameGen {}
+
+impl PilotFrameGen {
+    /// Creates a generator inserting one pilot every `pilot_spacing` symbols
+    /// into blocks of `payload_len` symbols.
+    pub fn create(payload_len: usize, pilot_spacing: usize) -> Result<PilotFrameGen> {
+        let q = created(unsafe { qpilotgen_create(payload_len as _, pilot_spacing as _) })?;
+        Ok(PilotFrameGen { q, payload_len })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qpilotgen_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn payload_len(&self) -> usize {
+        self.payload_len
+    }
+
+    /// Number of symbols in a frame, payload and pilots together.
+    pub fn frame_len(&self) -> usize {
+        unsafe { qpilotgen_get_frame_len(self.q) as usize }
+    }
+
+    /// Interleaves pilots with `payload`; fails with
+    /// [`Error::EIRANGE`](crate::Error::EIRANGE) unless `payload` is
+    /// `payload_len()` symbols.
+    pub fn execute(&mut self, payload: &[Complex32]) -> Result<Vec<Complex32>> {
+        check_len(payload.len(), self.payload_len)?;
+        let mut frame = vec![Complex32::default(); self.frame_len()];
+        liquid_try!(unsafe {
+            qpilotgen_execute(self.q, payload.as_ptr() as *mut _, frame.as_mut_ptr())
+        });
+        Ok(frame)
+    }
+}
+
+impl Clone for PilotFrameGen {
+    fn clone(&self) -> Self {
+        PilotFrameGen {
+            q: unsafe { qpilotgen_copy(self.q) },
+            payload_len: self.payload_len,
+        }
+    }
+}
+
+impl Drop for PilotFrameGen {
+    fn drop(&mut self) {
+        unsafe { qpilotgen_destroy(self.q) };
+    }
+}
+
+/// Recovers payload symbols from a [`PilotFrameGen`] frame, estimating carrier
+/// offset, phase and gain from the pilots (`qpilotsync`).
+pub struct PilotFrameSync {
+    q: qpilotsync,
+    payload_len: usize,
+}
+
+unsafe impl Send for PilotFrameSync {}
+
+impl PilotFrameSync {
+    pub fn create(payload_len: usize, pilot_spacing: usize) -> Result<PilotFrameSync> {
+        let q = created(unsafe { qpilotsync_create(payload_len as _, pilot_spacing as _) })?;
+        Ok(PilotFrameSync { q, payload_len })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { qpilotsync_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn payload_len(&self) -> usize {
+        self.payload_len
+    }
+
+    pub fn frame_len(&self) -> usize {
+        unsafe { qpilotsync_get_frame_len(self.q) as usize }
+    }
+
+    /// Strips pilots from `frame` and returns the corrected payload symbols;
+    /// fails with [`Error::EIRANGE`](crate::Error::EIRANGE) unless `frame`
+    /// is `frame_len()` symbols.
+    pub fn execute(&mut self, frame: &[Complex32]) -> Result<Vec<Complex32>> {
+        check_len(frame.len(), self.frame_len())?;
+        let mut payload = vec![Complex32::default(); self.payload_len];
+        liquid_try!(unsafe {
+            qpilotsync_execute(self.q, frame.as_ptr() as *mut _, payload.as_mut_ptr())
+        });
+        Ok(payload)
+    }
+
+    /// Estimated carrier frequency offset, in radians/symbol.
+    pub fn dphi(&self) -> f32 {
+        unsafe { qpilotsync_get_dphi(self.q) }
+    }
+
+    /// Estimated carrier phase offset, in radians.
+    pub fn phi(&self) -> f32 {
+        unsafe { qpilotsync_get_phi(self.q) }
+    }
+
+    /// Estimated channel gain.
+    pub fn gain(&self) -> f32 {
+        unsafe { qpilotsync_get_gain(self.q) }
+    }
+
+    /// Error vector magnitude of the pilots, in dB.
+    pub fn evm(&self) -> f32 {
+        unsafe { qpilotsync_get_evm(self.q) }
+    }
+}
+
+impl Clone for PilotFrameSync {
+    fn clone(&self) -> Self {
+        PilotFrameSync {
+            q: unsafe { qpilotsync_copy(self.q) },
+            payload_len: self.payload_len,
+        }
+    }
+}
+
+impl Drop for PilotFrameSync {
+    fn drop(&mut self) {
+        unsafe { qpilotsync_destroy(self.q) };
+    }
+}
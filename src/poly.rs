@@ -0,0 +1,287 @@
+//! Polynomials over liquid's `poly`/`polyf`/`polyc`/`polycf` routines.
+
+use liquidizers_sys::*;
+
+use std::os::raw::{c_int, c_uint};
+
+use crate::{sealed, Complex32, Complex64, Error, Result};
+
+/// Coefficient types with liquid polynomial implementations: `f64` (`poly`),
+/// `f32` (`polyf`), [`Complex64`] (`polyc`) and [`Complex32`] (`polycf`).
+pub trait PolynomialElement: sealed::Sealed + Copy + Default {
+    /// Type of the roots: the complex type of matching precision.
+    type Complex: Copy + Default;
+
+    #[doc(hidden)]
+    unsafe fn poly_val(p: *mut Self, k: c_uint, x: Self) -> Self;
+    #[doc(hidden)]
+    unsafe fn poly_fit(x: *mut Self, y: *mut Self, n: c_uint, p: *mut Self, k: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn poly_fit_lagrange(x: *mut Self, y: *mut Self, n: c_uint, p: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn poly_interp_lagrange(x: *mut Self, y: *mut Self, n: c_uint, x0: Self) -> Self;
+    #[doc(hidden)]
+    unsafe fn poly_findroots(p: *mut Self, n: c_uint, roots: *mut Self::Complex) -> c_int;
+    #[doc(hidden)]
+    unsafe fn poly_expandroots(r: *mut Self, n: c_uint, p: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn poly_expandroots2(a: *mut Self, b: *mut Self, n: c_uint, p: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn poly_expandbinomial(n: c_uint, p: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn poly_expandbinomial_pm(m: c_uint, k: c_uint, p: *mut Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn poly_mul(
+        a: *mut Self,
+        order_a: c_uint,
+        b: *mut Self,
+        order_b: c_uint,
+        c: *mut Self,
+    ) -> c_int;
+}
+
+macro_rules! impl_polynomial_element {
+    (
+        $t:ty, $c:ty,
+        $val:ident, $fit:ident, $fit_lagrange:ident, $interp_lagrange:ident, $findroots:ident,
+        $expandroots:ident, $expandroots2:ident, $expandbinomial:ident,
+        $expandbinomial_pm:ident, $mul:ident,
+    ) => {
+        impl PolynomialElement for $t {
+            type Complex = $c;
+
+            unsafe fn poly_val(p: *mut $t, k: c_uint, x: $t) -> $t {
+                $val(p, k, x)
+            }
+            unsafe fn poly_fit(x: *mut $t, y: *mut $t, n: c_uint, p: *mut $t, k: c_uint) -> c_int {
+                $fit(x, y, n, p, k)
+            }
+            unsafe fn poly_fit_lagrange(x: *mut $t, y: *mut $t, n: c_uint, p: *mut $t) -> c_int {
+                $fit_lagrange(x, y, n, p)
+            }
+            unsafe fn poly_interp_lagrange(x: *mut $t, y: *mut $t, n: c_uint, x0: $t) -> $t {
+                $interp_lagrange(x, y, n, x0)
+            }
+            unsafe fn poly_findroots(p: *mut $t, n: c_uint, roots: *mut $c) -> c_int {
+                $findroots(p, n, roots)
+            }
+            unsafe fn poly_expandroots(r: *mut $t, n: c_uint, p: *mut $t) -> c_int {
+                $expandroots(r, n, p)
+            }
+            unsafe fn poly_expandroots2(a: *mut $t, b: *mut $t, n: c_uint, p: *mut $t) -> c_int {
+                $expandroots2(a, b, n, p)
+            }
+            unsafe fn poly_expandbinomial(n: c_uint, p: *mut $t) -> c_int {
+                $expandbinomial(n, p)
+            }
+            unsafe fn poly_expandbinomial_pm(m: c_uint, k: c_uint, p: *mut $t) -> c_int {
+                $expandbinomial_pm(m, k, p)
+            }
+            unsafe fn poly_mul(
+                a: *mut $t,
+                order_a: c_uint,
+                b: *mut $t,
+                order_b: c_uint,
+                c: *mut $t,
+            ) -> c_int {
+                $mul(a, order_a, b, order_b, c)
+            }
+        }
+    };
+}
+
+impl_polynomial_element! {
+    f64, Complex64,
+    poly_val, poly_fit, poly_fit_lagrange, poly_interp_lagrange, poly_findroots,
+    poly_expandroots, poly_expandroots2, poly_expandbinomial,
+    poly_expandbinomial_pm, poly_mul,
+}
+
+impl_polynomial_element! {
+    f32, Complex32,
+    polyf_val, polyf_fit, polyf_fit_lagrange, polyf_interp_lagrange, polyf_findroots,
+    polyf_expandroots, polyf_expandroots2, polyf_expandbinomial,
+    polyf_expandbinomial_pm, polyf_mul,
+}
+
+impl_polynomial_element! {
+    Complex64, Complex64,
+    polyc_val, polyc_fit, polyc_fit_lagrange, polyc_interp_lagrange, polyc_findroots,
+    polyc_expandroots, polyc_expandroots2, polyc_expandbinomial,
+    polyc_expandbinomial_pm, polyc_mul,
+}
+
+impl_polynomial_element! {
+    Complex32, Complex32,
+    polycf_val, polycf_fit, polycf_fit_lagrange, polycf_interp_lagrange, polycf_findroots,
+    polycf_expandroots, polycf_expandroots2, polycf_expandbinomial,
+    polycf_expandbinomial_pm, polycf_mul,
+}
+
+/// Polynomial `p[0] + p[1]*x + ... + p[k-1]*x^(k-1)`, stored as its
+/// coefficients in ascending order of power.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polynomial<T: PolynomialElement> {
+    coefficients: Vec<T>,
+}
+
+fn check_points<T>(x: &[T], y: &[T]) -> Result<()> {
+    if x.is_empty() || x.len() != y.len() {
+        Err(Error::EIRANGE)
+    } else {
+        Ok(())
+    }
+}
+
+impl<T: PolynomialElement> Polynomial<T> {
+    /// Wraps `coefficients`, lowest power first.
+    pub fn new(coefficients: Vec<T>) -> Polynomial<T> {
+        Polynomial { coefficients }
+    }
+
+    /// Coefficients, lowest power first.
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.coefficients
+    }
+
+    /// Order of the polynomial, one less than the number of coefficients.
+    pub fn order(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    fn ptr(&self) -> *mut T {
+        self.coefficients.as_ptr() as *mut T
+    }
+
+    /// Evaluates the polynomial at `x`.
+    pub fn val(&self, x: T) -> T {
+        unsafe { T::poly_val(self.ptr(), self.coefficients.len() as _, x) }
+    }
+
+    /// Least-squares fit of `k` coefficients to the points `(x[i], y[i])`;
+    /// fails with [`Error::EIRANGE`] unless `x` and `y` have the same,
+    /// non-zero length and `1 <= k <= x.len()`.
+    pub fn fit(x: &[T], y: &[T], k: usize) -> Result<Polynomial<T>> {
+        check_points(x, y)?;
+        if k == 0 || k > x.len() {
+            return Err(Error::EIRANGE);
+        }
+        let mut p = vec![T::default(); k];
+        liquid_try!(unsafe {
+            T::poly_fit(
+                x.as_ptr() as *mut _,
+                y.as_ptr() as *mut _,
+                x.len() as _,
+                p.as_mut_ptr(),
+                k as _,
+            )
+        });
+        Ok(Polynomial::new(p))
+    }
+
+    /// Exact fit through all `n` points, giving `n` coefficients.
+    pub fn fit_lagrange(x: &[T], y: &[T]) -> Result<Polynomial<T>> {
+        check_points(x, y)?;
+        let mut p = vec![T::default(); x.len()];
+        liquid_try!(unsafe {
+            T::poly_fit_lagrange(
+                x.as_ptr() as *mut _,
+                y.as_ptr() as *mut _,
+                x.len() as _,
+                p.as_mut_ptr(),
+            )
+        });
+        Ok(Polynomial::new(p))
+    }
+
+    /// Evaluates the Lagrange polynomial through the points `(x[i], y[i])` at
+    /// `x0`, without computing its coefficients.
+    pub fn interp_lagrange(x: &[T], y: &[T], x0: T) -> Result<T> {
+        check_points(x, y)?;
+        Ok(unsafe {
+            T::poly_interp_lagrange(x.as_ptr() as *mut _, y.as_ptr() as *mut _, x.len() as _, x0)
+        })
+    }
+
+    /// The `order()` complex roots.
+    pub fn findroots(&self) -> Result<Vec<T::Complex>> {
+        if self.coefficients.len() < 2 {
+            return Err(Error::EIRANGE);
+        }
+        let mut roots = vec![T::Complex::default(); self.order()];
+        liquid_try!(unsafe {
+            T::poly_findroots(self.ptr(), self.coefficients.len() as _, roots.as_mut_ptr())
+        });
+        Ok(roots)
+    }
+
+    /// Expands `(x - r[0]) * (x - r[1]) * ...` into coefficients.
+    pub fn expandroots(r: &[T]) -> Result<Polynomial<T>> {
+        let mut p = vec![T::default(); r.len() + 1];
+        liquid_try!(unsafe {
+            T::poly_expandroots(r.as_ptr() as *mut _, r.len() as _, p.as_mut_ptr())
+        });
+        Ok(Polynomial::new(p))
+    }
+
+    /// Expands `(b[0]*x - a[0]) * (b[1]*x - a[1]) * ...` into coefficients;
+    /// fails with [`Error::EIRANGE`] unless `a` and `b` have the same length.
+    pub fn expandroots2(a: &[T], b: &[T]) -> Result<Polynomial<T>> {
+        if a.len() != b.len() {
+            return Err(Error::EIRANGE);
+        }
+        let mut p = vec![T::default(); a.len() + 1];
+        liquid_try!(unsafe {
+            T::poly_expandroots2(
+                a.as_ptr() as *mut _,
+                b.as_ptr() as *mut _,
+                a.len() as _,
+                p.as_mut_ptr(),
+            )
+        });
+        Ok(Polynomial::new(p))
+    }
+
+    /// Expands `(1 + x)^n`.
+    pub fn expandbinomial(n: usize) -> Result<Polynomial<T>> {
+        let mut p = vec![T::default(); n + 1];
+        liquid_try!(unsafe { T::poly_expandbinomial(n as _, p.as_mut_ptr()) });
+        Ok(Polynomial::new(p))
+    }
+
+    /// Expands `(1 + x)^m * (1 - x)^k`.
+    pub fn expandbinomial_pm(m: usize, k: usize) -> Result<Polynomial<T>> {
+        let mut p = vec![T::default(); m + k + 1];
+        liquid_try!(unsafe { T::poly_expandbinomial_pm(m as _, k as _, p.as_mut_ptr()) });
+        Ok(Polynomial::new(p))
+    }
+
+    /// Product of two polynomials; fails with [`Error::EIRANGE`] if either
+    /// has no coefficients.
+    pub fn mul(&self, rhs: &Polynomial<T>) -> Result<Polynomial<T>> {
+        if self.coefficients.is_empty() || rhs.coefficients.is_empty() {
+            return Err(Error::EIRANGE);
+        }
+        let mut c = vec![T::default(); self.order() + rhs.order() + 1];
+        liquid_try!(unsafe {
+            T::poly_mul(
+                self.ptr(),
+                self.order() as _,
+                rhs.ptr(),
+                rhs.order() as _,
+                c.as_mut_ptr(),
+            )
+        });
+        Ok(Polynomial::new(c))
+    }
+}
+
+impl<T: PolynomialElement> From<Vec<T>> for Polynomial<T> {
+    fn from(coefficients: Vec<T>) -> Polynomial<T> {
+        Polynomial::new(coefficients)
+    }
+}
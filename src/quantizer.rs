@@ -0,0 +1,267 @@
+//! Finite-resolution converter emulation (`quantizerf`, `quantize_adc`,
+//! `quantize_dac`).
+
+use liquidizers_sys::*;
+
+use std::marker::PhantomData;
+
+use crate::{created, sealed, Complex32, Error, Result};
+
+liquid_enum! {
+    /// Companding applied before quantization (`liquid_compander_type`).
+    pub enum CompanderType: liquid_compander_type {
+        None = liquid_compander_type_LIQUID_COMPANDER_NONE,
+        Linear = liquid_compander_type_LIQUID_COMPANDER_LINEAR,
+        Mulaw = liquid_compander_type_LIQUID_COMPANDER_MULAW,
+        Alaw = liquid_compander_type_LIQUID_COMPANDER_ALAW,
+    }
+}
+
+/// Quantizes `x` in `[-1, 1]` to a `num_bits`-bit code, clipping outside
+/// that range.
+pub fn quantize_adc(x: f32, num_bits: u32) -> u32 {
+    unsafe { liquidizers_sys::quantize_adc(x, num_bits) }
+}
+
+/// Inverse of [`quantize_adc`].
+pub fn quantize_dac(s: u32, num_bits: u32) -> f32 {
+    unsafe { liquidizers_sys::quantize_dac(s, num_bits) }
+}
+
+/// Sample types [`Quantizer`] accepts: `f32` and [`Complex32`].
+///
+/// liquid's `quantizercf` rejects complex samples, so both types go through
+/// `quantizerf`. A complex sample is quantized per component, and its code
+/// holds the real part's `num_bits` bits above the imaginary part's.
+pub trait QuantizerSample: sealed::Sealed + Copy + Default {
+    /// Components quantized per sample, each taking `num_bits` of the code.
+    #[doc(hidden)]
+    const COMPONENTS: u32;
+
+    #[doc(hidden)]
+    unsafe fn quantizer_execute_adc(q: quantizerf, num_bits: u32, x: Self) -> Result<u32>;
+    #[doc(hidden)]
+    unsafe fn quantizer_execute_dac(q: quantizerf, num_bits: u32, s: u32) -> Result<Self>;
+    #[doc(hidden)]
+    unsafe fn add_noise(x: *mut Self, nstd: f32);
+    #[doc(hidden)]
+    fn clip(self, limit: f32) -> Self;
+}
+
+unsafe fn adc(q: quantizerf, x: f32) -> Result<u32> {
+    let mut s = 0;
+    liquid_try!(quantizerf_execute_adc(q, x, &mut s));
+    Ok(s)
+}
+
+unsafe fn dac(q: quantizerf, s: u32) -> Result<f32> {
+    let mut x = 0.0;
+    liquid_try!(quantizerf_execute_dac(q, s, &mut x));
+    Ok(x)
+}
+
+impl QuantizerSample for f32 {
+    const COMPONENTS: u32 = 1;
+
+    unsafe fn quantizer_execute_adc(q: quantizerf, _num_bits: u32, x: f32) -> Result<u32> {
+        adc(q, x)
+    }
+    unsafe fn quantizer_execute_dac(q: quantizerf, _num_bits: u32, s: u32) -> Result<f32> {
+        dac(q, s)
+    }
+    unsafe fn add_noise(x: *mut f32, nstd: f32) {
+        awgn(x, nstd)
+    }
+    fn clip(self, limit: f32) -> f32 {
+        self.clamp(-limit, limit)
+    }
+}
+
+impl QuantizerSample for Complex32 {
+    const COMPONENTS: u32 = 2;
+
+    unsafe fn quantizer_execute_adc(q: quantizerf, num_bits: u32, x: Complex32) -> Result<u32> {
+        Ok(adc(q, x.re)? << num_bits | adc(q, x.im)?)
+    }
+    unsafe fn quantizer_execute_dac(q: quantizerf, num_bits: u32, s: u32) -> Result<Complex32> {
+        let mask = (1 << num_bits) - 1;
+        Ok(Complex32 {
+            re: dac(q, s >> num_bits & mask)?,
+            im: dac(q, s & mask)?,
+        })
+    }
+    unsafe fn add_noise(x: *mut Complex32, nstd: f32) {
+        cawgn(x, nstd)
+    }
+    fn clip(self, limit: f32) -> Complex32 {
+        Complex32 {
+            re: self.re.clamp(-limit, limit),
+            im: self.im.clamp(-limit, limit),
+        }
+    }
+}
+
+/// Quantizer over `[-range, range]` with optional companding (`quantizerf`).
+/// Complex samples are quantized per component, as described under
+/// [`QuantizerSample`].
+pub struct Quantizer<T: QuantizerSample> {
+    q: quantizerf,
+    compander: CompanderType,
+    range: f32,
+    num_bits: u32,
+    _sample: PhantomData<T>,
+}
+
+unsafe impl<T: QuantizerSample> Send for Quantizer<T> {}
+
+impl<T: QuantizerSample> Quantizer<T> {
+    /// Fails with [`Error::EICONFIG`] if the code would exceed 32 bits, that
+    /// is `num_bits > 16` for [`Complex32`].
+    pub fn create(compander: CompanderType, range: f32, num_bits: u32) -> Result<Quantizer<T>> {
+        if num_bits > 32 / T::COMPONENTS {
+            return Err(Error::EICONFIG);
+        }
+        let q = created(unsafe { quantizerf_create(compander.to_raw(), range, num_bits) })?;
+        Ok(Quantizer {
+            q,
+            compander,
+            range,
+            num_bits,
+            _sample: PhantomData,
+        })
+    }
+
+    pub fn compander(&self) -> CompanderType {
+        self.compander
+    }
+
+    pub fn range(&self) -> f32 {
+        self.range
+    }
+
+    pub fn num_bits(&self) -> u32 {
+        self.num_bits
+    }
+
+    pub fn adc(&mut self, x: T) -> Result<u32> {
+        unsafe { T::quantizer_execute_adc(self.q, self.num_bits, x) }
+    }
+
+    pub fn dac(&mut self, s: u32) -> Result<T> {
+        unsafe { T::quantizer_execute_dac(self.q, self.num_bits, s) }
+    }
+
+    pub fn adc_block(&mut self, x: &[T]) -> Result<Vec<u32>> {
+        x.iter().map(|&x| self.adc(x)).collect()
+    }
+
+    pub fn dac_block(&mut self, s: &[u32]) -> Result<Vec<T>> {
+        s.iter().map(|&s| self.dac(s)).collect()
+    }
+
+    /// Converts `x` to codes and back, giving the values the converter can
+    /// represent.
+    pub fn quantize(&mut self, x: &[T]) -> Result<Vec<T>> {
+        x.iter()
+            .map(|&x| self.adc(x).and_then(|s| self.dac(s)))
+            .collect()
+    }
+}
+
+impl<T: QuantizerSample> Drop for Quantizer<T> {
+    fn drop(&mut self) {
+        unsafe { quantizerf_destroy(self.q) };
+    }
+}
+
+/// Analog-to-digital converter model: optional input noise, clipping at full
+/// scale, then uniform quantization.
+///
+/// Noise comes from liquid's `awgn`/`cawgn`, which use the C library's
+/// `rand()`.
+pub struct AdcModel<T: QuantizerSample> {
+    quantizer: Quantizer<T>,
+    noise_std: Option<f32>,
+}
+
+impl<T: QuantizerSample> AdcModel<T> {
+    /// Creates a noiseless `num_bits`-bit converter clipping at
+    /// `±full_scale`; fails with [`Error::EICONFIG`] unless `full_scale > 0`.
+    pub fn create(num_bits: u32, full_scale: f32) -> Result<AdcModel<T>> {
+        if full_scale <= 0.0 {
+            return Err(Error::EICONFIG);
+        }
+        Ok(AdcModel {
+            quantizer: Quantizer::create(CompanderType::Linear, full_scale, num_bits)?,
+            noise_std: None,
+        })
+    }
+
+    /// Adds Gaussian noise of standard deviation `noise_std` ahead of the
+    /// converter.
+    pub fn with_noise(mut self, noise_std: f32) -> AdcModel<T> {
+        self.noise_std = Some(noise_std);
+        self
+    }
+
+    pub fn num_bits(&self) -> u32 {
+        self.quantizer.num_bits()
+    }
+
+    pub fn full_scale(&self) -> f32 {
+        self.quantizer.range()
+    }
+
+    pub fn noise_std(&self) -> Option<f32> {
+        self.noise_std
+    }
+
+    /// Signal-to-quantization-noise ratio of an ideal converter driven by a
+    /// full-scale sine, `6.02 N + 1.76` dB.
+    pub fn ideal_sqnr_db(&self) -> f32 {
+        6.02 * self.num_bits() as f32 + 1.76
+    }
+
+    /// Output codes for `x`.
+    pub fn sample(&mut self, x: &[T]) -> Result<Vec<u32>> {
+        let full_scale = self.full_scale();
+        let mut codes = Vec::with_capacity(x.len());
+        for &x in x {
+            let mut x = x;
+            if let Some(nstd) = self.noise_std {
+                unsafe { T::add_noise(&mut x, nstd) };
+            }
+            codes.push(self.quantizer.adc(x.clip(full_scale))?);
+        }
+        Ok(codes)
+    }
+
+    /// Reconstructed values for `x`, as seen after an ideal DAC.
+    pub fn process(&mut self, x: &[T]) -> Result<Vec<T>> {
+        let codes = self.sample(x)?;
+        self.quantizer.dac_block(&codes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complex_round_trip() {
+        let num_bits = 8;
+        let step = 2.0 / (1 << num_bits) as f32;
+        let mut real = Quantizer::<f32>::create(CompanderType::Linear, 1.0, num_bits).unwrap();
+        let mut complex =
+            Quantizer::<Complex32>::create(CompanderType::Linear, 1.0, num_bits).unwrap();
+        for &(re, im) in &[(0.0, 0.0), (0.3, -0.7), (-0.99, 0.51), (0.125, 0.875)] {
+            let x = Complex32 { re, im };
+            let s = complex.adc(x).unwrap();
+            assert_eq!(s >> num_bits, real.adc(re).unwrap());
+            assert_eq!(s & ((1 << num_bits) - 1), real.adc(im).unwrap());
+            let y = complex.dac(s).unwrap();
+            assert!((y.re - re).abs() <= step && (y.im - im).abs() <= step);
+        }
+        assert!(Quantizer::<Complex32>::create(CompanderType::Linear, 1.0, 17).is_err());
+    }
+}
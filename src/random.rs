@@ -0,0 +1,327 @@
+//! Random variates using liquid's parameterisations.
+//!
+//! liquid's own generators (`randnf`, `randricekf`, ...) draw from the C
+//! library's global `rand()` state. The types here evaluate liquid's
+//! densities and, with the `rand` feature, sample from a caller-supplied RNG
+//! instead, so each thread can run a reproducible stream.
+
+use liquidizers_sys::*;
+
+use crate::{Error, Result};
+
+/// Probability density and cumulative distribution of a real random variable.
+pub trait Density {
+    /// Probability density at `x`.
+    fn pdf(&self, x: f32) -> f32;
+    /// Probability that a variate is no greater than `x`.
+    fn cdf(&self, x: f32) -> f32;
+}
+
+fn check(valid: bool) -> Result<()> {
+    if valid {
+        Ok(())
+    } else {
+        Err(Error::EICONFIG)
+    }
+}
+
+/// Uniform distribution on `[a, b)` (`randuf`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Uniform {
+    a: f32,
+    b: f32,
+}
+
+impl Uniform {
+    /// Fails with [`Error::EICONFIG`] unless `a < b`.
+    pub fn new(a: f32, b: f32) -> Result<Uniform> {
+        check(a < b)?;
+        Ok(Uniform { a, b })
+    }
+}
+
+impl Density for Uniform {
+    fn pdf(&self, x: f32) -> f32 {
+        unsafe { randuf_pdf(x, self.a, self.b) }
+    }
+
+    fn cdf(&self, x: f32) -> f32 {
+        unsafe { randuf_cdf(x, self.a, self.b) }
+    }
+}
+
+/// Gaussian distribution with mean `eta` and standard deviation `sigma`
+/// (`randnf`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Normal {
+    eta: f32,
+    sigma: f32,
+}
+
+impl Normal {
+    /// Fails with [`Error::EICONFIG`] unless `sigma > 0`.
+    pub fn new(eta: f32, sigma: f32) -> Result<Normal> {
+        check(sigma > 0.0)?;
+        Ok(Normal { eta, sigma })
+    }
+
+    /// Zero mean, unit variance, as drawn by `randnf`.
+    pub fn standard() -> Normal {
+        Normal {
+            eta: 0.0,
+            sigma: 1.0,
+        }
+    }
+}
+
+impl Density for Normal {
+    fn pdf(&self, x: f32) -> f32 {
+        unsafe { randnf_pdf(x, self.eta, self.sigma) }
+    }
+
+    fn cdf(&self, x: f32) -> f32 {
+        unsafe { randnf_cdf(x, self.eta, self.sigma) }
+    }
+}
+
+/// Circular complex Gaussian with total variance `sigma^2`, split evenly
+/// between the real and imaginary parts (`crandnf`, `cawgn`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComplexNormal {
+    sigma: f32,
+}
+
+impl ComplexNormal {
+    /// Fails with [`Error::EICONFIG`] unless `sigma > 0`.
+    pub fn new(sigma: f32) -> Result<ComplexNormal> {
+        check(sigma > 0.0)?;
+        Ok(ComplexNormal { sigma })
+    }
+}
+
+/// Exponential distribution with rate `lambda` (`randexpf`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Exponential {
+    lambda: f32,
+}
+
+impl Exponential {
+    /// Fails with [`Error::EICONFIG`] unless `lambda > 0`.
+    pub fn new(lambda: f32) -> Result<Exponential> {
+        check(lambda > 0.0)?;
+        Ok(Exponential { lambda })
+    }
+}
+
+impl Density for Exponential {
+    fn pdf(&self, x: f32) -> f32 {
+        unsafe { randexpf_pdf(x, self.lambda) }
+    }
+
+    fn cdf(&self, x: f32) -> f32 {
+        unsafe { randexpf_cdf(x, self.lambda) }
+    }
+}
+
+/// Weibull distribution with shape `alpha`, scale `beta` and offset `gamma`
+/// (`randweibf`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weibull {
+    alpha: f32,
+    beta: f32,
+    gamma: f32,
+}
+
+impl Weibull {
+    /// Fails with [`Error::EICONFIG`] unless `alpha` and `beta` are positive.
+    pub fn new(alpha: f32, beta: f32, gamma: f32) -> Result<Weibull> {
+        check(alpha > 0.0 && beta > 0.0)?;
+        Ok(Weibull { alpha, beta, gamma })
+    }
+}
+
+impl Density for Weibull {
+    fn pdf(&self, x: f32) -> f32 {
+        unsafe { randweibf_pdf(x, self.alpha, self.beta, self.gamma) }
+    }
+
+    fn cdf(&self, x: f32) -> f32 {
+        unsafe { randweibf_cdf(x, self.alpha, self.beta, self.gamma) }
+    }
+}
+
+/// Gamma distribution with shape `alpha` and scale `beta` (`randgammaf`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gamma {
+    alpha: f32,
+    beta: f32,
+}
+
+impl Gamma {
+    /// Fails with [`Error::EICONFIG`] unless `alpha` and `beta` are positive.
+    pub fn new(alpha: f32, beta: f32) -> Result<Gamma> {
+        check(alpha > 0.0 && beta > 0.0)?;
+        Ok(Gamma { alpha, beta })
+    }
+}
+
+impl Density for Gamma {
+    fn pdf(&self, x: f32) -> f32 {
+        unsafe { randgammaf_pdf(x, self.alpha, self.beta) }
+    }
+
+    fn cdf(&self, x: f32) -> f32 {
+        unsafe { randgammaf_cdf(x, self.alpha, self.beta) }
+    }
+}
+
+/// Nakagami-m fading envelope with shape `m` and spread `omega`, the mean
+/// power (`randnakmf`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Nakagami {
+    m: f32,
+    omega: f32,
+}
+
+impl Nakagami {
+    /// Fails with [`Error::EICONFIG`] unless `m >= 0.5` and `omega > 0`.
+    pub fn new(m: f32, omega: f32) -> Result<Nakagami> {
+        check(m >= 0.5 && omega > 0.0)?;
+        Ok(Nakagami { m, omega })
+    }
+}
+
+impl Density for Nakagami {
+    fn pdf(&self, x: f32) -> f32 {
+        unsafe { randnakmf_pdf(x, self.m, self.omega) }
+    }
+
+    fn cdf(&self, x: f32) -> f32 {
+        unsafe { randnakmf_cdf(x, self.m, self.omega) }
+    }
+}
+
+/// Rice-K fading envelope with K-factor `k`, the ratio of line-of-sight to
+/// scattered power, and mean power `omega` (`randricekf`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RiceK {
+    k: f32,
+    omega: f32,
+}
+
+impl RiceK {
+    /// Fails with [`Error::EICONFIG`] unless `k >= 0` and `omega > 0`.
+    pub fn new(k: f32, omega: f32) -> Result<RiceK> {
+        check(k >= 0.0 && omega > 0.0)?;
+        Ok(RiceK { k, omega })
+    }
+}
+
+impl Density for RiceK {
+    fn pdf(&self, x: f32) -> f32 {
+        unsafe { randricekf_pdf(x, self.k, self.omega) }
+    }
+
+    fn cdf(&self, x: f32) -> f32 {
+        unsafe { randricekf_cdf(x, self.k, self.omega) }
+    }
+}
+
+#[cfg(feature = "rand")]
+mod sampling {
+    use rand::distributions::Distribution;
+    use rand::Rng;
+
+    use std::f32::consts::{FRAC_1_SQRT_2, PI};
+
+    use super::*;
+    use crate::Complex32;
+
+    /// Uniform on `(0, 1]`, safe to take the logarithm of.
+    fn open_unit<R: Rng + ?Sized>(rng: &mut R) -> f32 {
+        1.0 - rng.gen::<f32>()
+    }
+
+    /// Pair of independent standard normals (Box-Muller).
+    fn standard_normal_pair<R: Rng + ?Sized>(rng: &mut R) -> (f32, f32) {
+        let r = (-2.0 * open_unit(rng).ln()).sqrt();
+        let theta = 2.0 * PI * rng.gen::<f32>();
+        (r * theta.cos(), r * theta.sin())
+    }
+
+    /// Marsaglia-Tsang, boosted for `alpha < 1`.
+    fn standard_gamma<R: Rng + ?Sized>(rng: &mut R, alpha: f32) -> f32 {
+        if alpha < 1.0 {
+            return standard_gamma(rng, alpha + 1.0) * open_unit(rng).powf(1.0 / alpha);
+        }
+        let d = alpha - 1.0 / 3.0;
+        let c = 1.0 / (9.0 * d).sqrt();
+        loop {
+            let (x, _) = standard_normal_pair(rng);
+            let v = (1.0 + c * x).powi(3);
+            if v <= 0.0 {
+                continue;
+            }
+            let u = open_unit(rng);
+            if u.ln() < 0.5 * x * x + d - d * v + d * v.ln() {
+                return d * v;
+            }
+        }
+    }
+
+    impl Distribution<f32> for Uniform {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+            self.a + (self.b - self.a) * rng.gen::<f32>()
+        }
+    }
+
+    impl Distribution<f32> for Normal {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+            self.eta + self.sigma * standard_normal_pair(rng).0
+        }
+    }
+
+    impl Distribution<Complex32> for ComplexNormal {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Complex32 {
+            let (re, im) = standard_normal_pair(rng);
+            let scale = self.sigma * FRAC_1_SQRT_2;
+            Complex32 {
+                re: scale * re,
+                im: scale * im,
+            }
+        }
+    }
+
+    impl Distribution<f32> for Exponential {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+            -open_unit(rng).ln() / self.lambda
+        }
+    }
+
+    impl Distribution<f32> for Weibull {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+            self.gamma + self.beta * (-open_unit(rng).ln()).powf(1.0 / self.alpha)
+        }
+    }
+
+    impl Distribution<f32> for Gamma {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+            self.beta * standard_gamma(rng, self.alpha)
+        }
+    }
+
+    impl Distribution<f32> for Nakagami {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+            (self.omega / self.m * standard_gamma(rng, self.m)).sqrt()
+        }
+    }
+
+    impl Distribution<f32> for RiceK {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+            let s = (self.omega * self.k / (self.k + 1.0)).sqrt();
+            let sigma = (0.5 * self.omega / (self.k + 1.0)).sqrt();
+            let (x, y) = standard_normal_pair(rng);
+            (s + sigma * x).hypot(sigma * y)
+        }
+    }
+}
@@ -0,0 +1,37 @@
+//! Data whitening (`scramble_data`).
+
+use liquidizers_sys::*;
+
+use crate::{Error, Result};
+
+/// Whitens data with liquid's fixed scrambling mask (`scramble_data`), so
+/// long runs of equal bits do not starve timing recovery. Scrambling is
+/// stateless; the same mask is applied from the start of every call.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scrambler;
+
+impl Scrambler {
+    pub fn new() -> Scrambler {
+        Scrambler
+    }
+
+    /// Scrambles `data` in place.
+    pub fn scramble(&self, data: &mut [u8]) {
+        unsafe { scramble_data(data.as_mut_ptr(), data.len() as _) };
+    }
+
+    /// Inverse of [`scramble`](Self::scramble).
+    pub fn unscramble(&self, data: &mut [u8]) {
+        unsafe { unscramble_data(data.as_mut_ptr(), data.len() as _) };
+    }
+
+    /// Unscrambles soft bits in place, one byte per bit; fails with
+    /// [`Error::EIRANGE`] unless they make up whole scrambled bytes.
+    pub fn unscramble_soft(&self, soft_bits: &mut [u8]) -> Result<()> {
+        if !soft_bits.len().is_multiple_of(8) {
+            return Err(Error::EIRANGE);
+        }
+        unsafe { unscramble_data_soft(soft_bits.as_mut_ptr(), (soft_bits.len() / 8) as _) };
+        Ok(())
+    }
+}
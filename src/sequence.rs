@@ -0,0 +1,259 @@
+//! Binary sequences: linear-feedback m-sequences (`msequence`) and packed bit
+//! sequences (`bsequence`).
+
+use liquidizers_sys::*;
+
+use std::ops::{BitAnd, BitXor};
+
+use crate::{created, Error, Result};
+
+/// Maximal-length sequence generated by a linear-feedback shift register
+/// (`msequence`).
+pub struct MSequence {
+    q: msequence,
+}
+
+unsafe impl Send for MSequence {}
+
+impl MSequence {
+    /// Creates a generator with `m`-bit shift register, generator polynomial
+    /// `g` and initial state `a`.
+    pub fn create(m: u32, g: u32, a: u32) -> Result<MSequence> {
+        let q = created(unsafe { msequence_create(m, g, a) })?;
+        Ok(MSequence { q })
+    }
+
+    /// Creates a generator from the polynomial `g` alone, deriving `m` from
+    /// its highest set bit.
+    pub fn create_genpoly(g: u32) -> Result<MSequence> {
+        let q = created(unsafe { msequence_create_genpoly(g) })?;
+        Ok(MSequence { q })
+    }
+
+    /// Creates a generator with liquid's default polynomial for `m` bits.
+    pub fn create_default(m: u32) -> Result<MSequence> {
+        let q = created(unsafe { msequence_create_default(m) })?;
+        Ok(MSequence { q })
+    }
+
+    /// Restores the initial state.
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { msequence_reset(self.q) });
+        Ok(())
+    }
+
+    /// Shifts the register once and returns the output bit.
+    pub fn advance(&mut self) -> bool {
+        unsafe { msequence_advance(self.q) != 0 }
+    }
+
+    /// Advances `bps` times and packs the output bits, first bit most
+    /// significant.
+    pub fn generate_symbol(&mut self, bps: u32) -> u32 {
+        unsafe { msequence_generate_symbol(self.q, bps) }
+    }
+
+    /// Shift register length `m`.
+    pub fn m(&self) -> u32 {
+        unsafe { msequence_get_genpoly_length(self.q) }
+    }
+
+    pub fn genpoly(&self) -> u32 {
+        unsafe { msequence_get_genpoly(self.q) }
+    }
+
+    /// Nominal period, `2^m - 1`.
+    pub fn period(&self) -> u32 {
+        unsafe { msequence_get_length(self.q) }
+    }
+
+    /// Period found by running the register until it repeats; shorter than
+    /// [`period`](Self::period) if the polynomial is not primitive.
+    pub fn measure_period(&mut self) -> u32 {
+        unsafe { msequence_measure_period(self.q) }
+    }
+
+    /// Measured period of the generator polynomial `g`.
+    pub fn genpoly_period(g: u32) -> u32 {
+        unsafe { msequence_genpoly_period(g) }
+    }
+
+    pub fn state(&self) -> u32 {
+        unsafe { msequence_get_state(self.q) }
+    }
+
+    pub fn set_state(&mut self, a: u32) -> Result<()> {
+        liquid_try!(unsafe { msequence_set_state(self.q, a) });
+        Ok(())
+    }
+}
+
+/// Endless stream of output bits.
+impl Iterator for MSequence {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        Some(self.advance())
+    }
+}
+
+impl Clone for MSequence {
+    fn clone(&self) -> Self {
+        MSequence {
+            q: unsafe { msequence_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for MSequence {
+    fn drop(&mut self) {
+        unsafe { msequence_destroy(self.q) };
+    }
+}
+
+/// Fixed-length binary sequence (`bsequence`).
+pub struct BSequence {
+    q: bsequence,
+}
+
+unsafe impl Send for BSequence {}
+
+impl BSequence {
+    /// Creates an all-zero sequence of `num_bits` bits.
+    pub fn create(num_bits: u32) -> Result<BSequence> {
+        let q = created(unsafe { bsequence_create(num_bits) })?;
+        Ok(BSequence { q })
+    }
+
+    /// Creates a sequence of one full period of `ms`, advancing it.
+    pub fn from_msequence(ms: &mut MSequence) -> Result<BSequence> {
+        let bs = BSequence::create(ms.period())?;
+        liquid_try!(unsafe { bsequence_init_msequence(bs.q, ms.q) });
+        Ok(bs)
+    }
+
+    /// Creates a pair of complementary codes of `num_bits` bits each, whose
+    /// autocorrelations sum to zero at every non-zero lag. `num_bits` must be
+    /// a power of two, at least 8.
+    pub fn complementary_codes(num_bits: u32) -> Result<(BSequence, BSequence)> {
+        let a = BSequence::create(num_bits)?;
+        let b = BSequence::create(num_bits)?;
+        liquid_try!(unsafe { bsequence_create_ccodes(a.q, b.q) });
+        Ok((a, b))
+    }
+
+    /// Clears every bit.
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { bsequence_reset(self.q) });
+        Ok(())
+    }
+
+    /// Loads the bits from `v`, packed eight per byte; fails with
+    /// [`Error::EIRANGE`] unless `v` holds exactly `ceil(len() / 8)` bytes.
+    pub fn init(&mut self, v: &[u8]) -> Result<()> {
+        if v.len() != self.len().div_ceil(8) {
+            return Err(Error::EIRANGE);
+        }
+        liquid_try!(unsafe { bsequence_init(self.q, v.as_ptr() as *mut _) });
+        Ok(())
+    }
+
+    /// Shifts the sequence left one bit, appending `bit`.
+    pub fn push(&mut self, bit: bool) -> Result<()> {
+        liquid_try!(unsafe { bsequence_push(self.q, bit as _) });
+        Ok(())
+    }
+
+    /// Rotates the sequence left one bit.
+    pub fn circshift(&mut self) -> Result<()> {
+        liquid_try!(unsafe { bsequence_circshift(self.q) });
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { bsequence_get_length(self.q) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Bit `i`; fails with [`Error::EIRANGE`] if `i >= len()`.
+    pub fn get(&self, i: usize) -> Result<bool> {
+        if i >= self.len() {
+            return Err(Error::EIRANGE);
+        }
+        Ok(unsafe { bsequence_index(self.q, i as _) != 0 })
+    }
+
+    /// Number of set bits.
+    pub fn accumulate(&self) -> u32 {
+        unsafe { bsequence_accumulate(self.q) }
+    }
+
+    /// Number of positions at which the sequences agree; fails with
+    /// [`Error::EIRANGE`] unless the lengths match.
+    pub fn correlate(&self, other: &BSequence) -> Result<i32> {
+        if self.len() != other.len() {
+            return Err(Error::EIRANGE);
+        }
+        Ok(unsafe { bsequence_correlate(self.q, other.q) })
+    }
+
+    /// Bitwise XOR; fails with [`Error::EIRANGE`] unless the lengths match.
+    pub fn checked_xor(&self, rhs: &BSequence) -> Result<BSequence> {
+        if self.len() != rhs.len() {
+            return Err(Error::EIRANGE);
+        }
+        let z = BSequence::create(self.len() as _)?;
+        liquid_try!(unsafe { bsequence_add(self.q, rhs.q, z.q) });
+        Ok(z)
+    }
+
+    /// Bitwise AND; fails with [`Error::EIRANGE`] unless the lengths match.
+    pub fn checked_and(&self, rhs: &BSequence) -> Result<BSequence> {
+        if self.len() != rhs.len() {
+            return Err(Error::EIRANGE);
+        }
+        let z = BSequence::create(self.len() as _)?;
+        liquid_try!(unsafe { bsequence_mul(self.q, rhs.q, z.q) });
+        Ok(z)
+    }
+}
+
+macro_rules! impl_bsequence_op {
+    ($op:ident, $method:ident, $checked:ident) => {
+        impl<'a, 'b> $op<&'b BSequence> for &'a BSequence {
+            type Output = BSequence;
+
+            fn $method(self, rhs: &'b BSequence) -> BSequence {
+                match self.$checked(rhs) {
+                    Ok(z) => z,
+                    Err(_) => panic!(
+                        "bsequence length mismatch: {} {} {}",
+                        self.len(),
+                        stringify!($method),
+                        rhs.len()
+                    ),
+                }
+            }
+        }
+
+        impl $op<BSequence> for BSequence {
+            type Output = BSequence;
+
+            fn $method(self, rhs: BSequence) -> BSequence {
+                (&self).$method(&rhs)
+            }
+        }
+    };
+}
+
+impl_bsequence_op!(BitXor, bitxor, checked_xor);
+impl_bsequence_op!(BitAnd, bitand, checked_and);
+
+impl Drop for BSequence {
+    fn drop(&mut self) {
+        unsafe { bsequence_destroy(self.q) };
+    }
+}
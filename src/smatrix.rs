@@ -0,0 +1,402 @@
+//! Sparse matrices (`smatrixb`/`smatrixf`/`smatrixi`).
+
+use liquidizers_sys::*;
+
+use std::os::raw::{c_int, c_short, c_uchar, c_uint};
+
+use crate::{created, sealed, Error, Result};
+
+/// Element types with liquid sparse matrix implementations: `bool` and `u8`
+/// (`smatrixb`, binary elements with GF(2) arithmetic), `f32` (`smatrixf`)
+/// and `i16` (`smatrixi`).
+///
+/// `smatrixb` stores single bits, so `u8` elements must be 0 or 1; other
+/// values are rejected with [`Error::EIVAL`].
+pub trait SparseElement: sealed::Sealed + Copy + Default + PartialEq {
+    #[doc(hidden)]
+    type SMatrix;
+
+    #[doc(hidden)]
+    unsafe fn smatrix_create(m: c_uint, n: c_uint) -> *mut Self::SMatrix;
+    #[doc(hidden)]
+    unsafe fn smatrix_create_array(x: *mut Self, m: c_uint, n: c_uint) -> *mut Self::SMatrix;
+    #[doc(hidden)]
+    unsafe fn smatrix_destroy(q: *mut Self::SMatrix) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_size(q: *mut Self::SMatrix, m: *mut c_uint, n: *mut c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_clear(q: *mut Self::SMatrix) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_reset(q: *mut Self::SMatrix) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_isset(q: *mut Self::SMatrix, m: c_uint, n: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_insert(q: *mut Self::SMatrix, m: c_uint, n: c_uint, v: Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_delete(q: *mut Self::SMatrix, m: c_uint, n: c_uint) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_set(q: *mut Self::SMatrix, m: c_uint, n: c_uint, v: Self) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_get(q: *mut Self::SMatrix, m: c_uint, n: c_uint) -> Self;
+    #[doc(hidden)]
+    unsafe fn smatrix_eye(q: *mut Self::SMatrix) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_mul(
+        x: *mut Self::SMatrix,
+        y: *mut Self::SMatrix,
+        z: *mut Self::SMatrix,
+    ) -> c_int;
+    #[doc(hidden)]
+    unsafe fn smatrix_vmul(q: *mut Self::SMatrix, x: &[Self], y: &mut [Self]) -> c_int;
+    #[doc(hidden)]
+    fn is_valid(self) -> bool;
+}
+
+macro_rules! impl_sparse_element {
+    (
+        $t:ty, $raw:ty, $sm:ty {
+            $create:ident, $create_array:ident, $destroy:ident, $size:ident, $clear:ident,
+            $reset:ident, $isset:ident, $insert:ident, $delete:ident, $set:ident, $get:ident,
+            $eye:ident, $mul:ident, $vmul:ident,
+        },
+        |$v:ident| $valid:expr,
+    ) => {
+        impl SparseElement for $t {
+            type SMatrix = $sm;
+
+            unsafe fn smatrix_create(m: c_uint, n: c_uint) -> *mut $sm {
+                $create(m, n)
+            }
+            unsafe fn smatrix_create_array(x: *mut $t, m: c_uint, n: c_uint) -> *mut $sm {
+                $create_array(x as *mut $raw, m, n)
+            }
+            unsafe fn smatrix_destroy(q: *mut $sm) -> c_int {
+                $destroy(q)
+            }
+            unsafe fn smatrix_size(q: *mut $sm, m: *mut c_uint, n: *mut c_uint) -> c_int {
+                $size(q, m, n)
+            }
+            unsafe fn smatrix_clear(q: *mut $sm) -> c_int {
+                $clear(q)
+            }
+            unsafe fn smatrix_reset(q: *mut $sm) -> c_int {
+                $reset(q)
+            }
+            unsafe fn smatrix_isset(q: *mut $sm, m: c_uint, n: c_uint) -> c_int {
+                $isset(q, m, n)
+            }
+            unsafe fn smatrix_insert(q: *mut $sm, m: c_uint, n: c_uint, v: $t) -> c_int {
+                $insert(q, m, n, v as $raw)
+            }
+            unsafe fn smatrix_delete(q: *mut $sm, m: c_uint, n: c_uint) -> c_int {
+                $delete(q, m, n)
+            }
+            unsafe fn smatrix_set(q: *mut $sm, m: c_uint, n: c_uint, v: $t) -> c_int {
+                $set(q, m, n, v as $raw)
+            }
+            unsafe fn smatrix_get(q: *mut $sm, m: c_uint, n: c_uint) -> $t {
+                $get(q, m, n) as $t
+            }
+            unsafe fn smatrix_eye(q: *mut $sm) -> c_int {
+                $eye(q)
+            }
+            unsafe fn smatrix_mul(x: *mut $sm, y: *mut $sm, z: *mut $sm) -> c_int {
+                $mul(x, y, z)
+            }
+            unsafe fn smatrix_vmul(q: *mut $sm, x: &[$t], y: &mut [$t]) -> c_int {
+                $vmul(q, x.as_ptr() as *mut $raw, y.as_mut_ptr() as *mut $raw)
+            }
+            fn is_valid(self) -> bool {
+                let $v = self;
+                $valid
+            }
+        }
+    };
+}
+
+impl_sparse_element! {
+    u8, c_uchar, smatrixb_s {
+        smatrixb_create, smatrixb_create_array, smatrixb_destroy, smatrixb_size, smatrixb_clear,
+        smatrixb_reset, smatrixb_isset, smatrixb_insert, smatrixb_delete, smatrixb_set,
+        smatrixb_get, smatrixb_eye, smatrixb_mul, smatrixb_vmul,
+    },
+    |v| v <= 1,
+}
+
+impl_sparse_element! {
+    f32, f32, smatrixf_s {
+        smatrixf_create, smatrixf_create_array, smatrixf_destroy, smatrixf_size, smatrixf_clear,
+        smatrixf_reset, smatrixf_isset, smatrixf_insert, smatrixf_delete, smatrixf_set,
+        smatrixf_get, smatrixf_eye, smatrixf_mul, smatrixf_vmul,
+    },
+    |_v| true,
+}
+
+impl_sparse_element! {
+    i16, c_short, smatrixi_s {
+        smatrixi_create, smatrixi_create_array, smatrixi_destroy, smatrixi_size, smatrixi_clear,
+        smatrixi_reset, smatrixi_isset, smatrixi_insert, smatrixi_delete, smatrixi_set,
+        smatrixi_get, smatrixi_eye, smatrixi_mul, smatrixi_vmul,
+    },
+    |_v| true,
+}
+
+// `bool` shares `smatrixb` with `u8`, converting at the boundary so liquid
+// never writes into `bool` storage directly.
+impl SparseElement for bool {
+    type SMatrix = smatrixb_s;
+
+    unsafe fn smatrix_create(m: c_uint, n: c_uint) -> *mut smatrixb_s {
+        u8::smatrix_create(m, n)
+    }
+    unsafe fn smatrix_create_array(x: *mut bool, m: c_uint, n: c_uint) -> *mut smatrixb_s {
+        u8::smatrix_create_array(x as *mut u8, m, n)
+    }
+    unsafe fn smatrix_destroy(q: *mut smatrixb_s) -> c_int {
+        u8::smatrix_destroy(q)
+    }
+    unsafe fn smatrix_size(q: *mut smatrixb_s, m: *mut c_uint, n: *mut c_uint) -> c_int {
+        u8::smatrix_size(q, m, n)
+    }
+    unsafe fn smatrix_clear(q: *mut smatrixb_s) -> c_int {
+        u8::smatrix_clear(q)
+    }
+    unsafe fn smatrix_reset(q: *mut smatrixb_s) -> c_int {
+        u8::smatrix_reset(q)
+    }
+    unsafe fn smatrix_isset(q: *mut smatrixb_s, m: c_uint, n: c_uint) -> c_int {
+        u8::smatrix_isset(q, m, n)
+    }
+    unsafe fn smatrix_insert(q: *mut smatrixb_s, m: c_uint, n: c_uint, v: bool) -> c_int {
+        u8::smatrix_insert(q, m, n, v as u8)
+    }
+    unsafe fn smatrix_delete(q: *mut smatrixb_s, m: c_uint, n: c_uint) -> c_int {
+        u8::smatrix_delete(q, m, n)
+    }
+    unsafe fn smatrix_set(q: *mut smatrixb_s, m: c_uint, n: c_uint, v: bool) -> c_int {
+        u8::smatrix_set(q, m, n, v as u8)
+    }
+    unsafe fn smatrix_get(q: *mut smatrixb_s, m: c_uint, n: c_uint) -> bool {
+        u8::smatrix_get(q, m, n) != 0
+    }
+    unsafe fn smatrix_eye(q: *mut smatrixb_s) -> c_int {
+        u8::smatrix_eye(q)
+    }
+    unsafe fn smatrix_mul(x: *mut smatrixb_s, y: *mut smatrixb_s, z: *mut smatrixb_s) -> c_int {
+        u8::smatrix_mul(x, y, z)
+    }
+    unsafe fn smatrix_vmul(q: *mut smatrixb_s, x: &[bool], y: &mut [bool]) -> c_int {
+        let x: Vec<u8> = x.iter().map(|&x| x as u8).collect();
+        let mut bits = vec![0; y.len()];
+        let rc = u8::smatrix_vmul(q, &x, &mut bits);
+        for (y, b) in y.iter_mut().zip(bits) {
+            *y = b != 0;
+        }
+        rc
+    }
+    fn is_valid(self) -> bool {
+        true
+    }
+}
+
+fn check_values<'a, T: SparseElement + 'a>(values: impl IntoIterator<Item = &'a T>) -> Result<()> {
+    if values.into_iter().all(|v| v.is_valid()) {
+        Ok(())
+    } else {
+        Err(Error::EIVAL)
+    }
+}
+
+/// Sparse matrix storing only its non-zero elements.
+///
+/// `SparseMatrix<bool>` and `SparseMatrix<u8>` hold binary elements and
+/// multiply over GF(2), which suits parity-check matrices.
+pub struct SparseMatrix<T: SparseElement> {
+    q: *mut T::SMatrix,
+}
+
+unsafe impl<T: SparseElement> Send for SparseMatrix<T> {}
+
+impl<T: SparseElement> SparseMatrix<T> {
+    /// Creates an all-zero `rows` by `cols` matrix.
+    pub fn create(rows: usize, cols: usize) -> Result<SparseMatrix<T>> {
+        let q = created(unsafe { T::smatrix_create(rows as _, cols as _) })?;
+        Ok(SparseMatrix { q })
+    }
+
+    /// Creates an `n` by `n` identity matrix.
+    pub fn identity(n: usize) -> Result<SparseMatrix<T>> {
+        let m = SparseMatrix::create(n, n)?;
+        liquid_try!(unsafe { T::smatrix_eye(m.q) });
+        Ok(m)
+    }
+
+    /// Creates a matrix from dense rows; fails with [`Error::EIRANGE`] unless
+    /// every row has the same length, and with [`Error::EIVAL`] on a
+    /// non-binary `u8` element.
+    pub fn from_dense(rows: &[Vec<T>]) -> Result<SparseMatrix<T>> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != cols) {
+            return Err(Error::EIRANGE);
+        }
+        check_values(rows.iter().flatten())?;
+        let mut x: Vec<T> = rows.concat();
+        let q = created(unsafe {
+            T::smatrix_create_array(x.as_mut_ptr(), rows.len() as _, cols as _)
+        })?;
+        Ok(SparseMatrix { q })
+    }
+
+    /// Expands the matrix into dense rows.
+    pub fn to_dense(&self) -> Vec<Vec<T>> {
+        let (rows, cols) = self.size();
+        (0..rows)
+            .map(|r| {
+                (0..cols)
+                    .map(|c| unsafe { T::smatrix_get(self.q, r as _, c as _) })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Dimensions as `(rows, cols)`.
+    pub fn size(&self) -> (usize, usize) {
+        let mut m = 0;
+        let mut n = 0;
+        unsafe { T::smatrix_size(self.q, &mut m, &mut n) };
+        (m as usize, n as usize)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.size().0
+    }
+
+    pub fn cols(&self) -> usize {
+        self.size().1
+    }
+
+    fn check_index(&self, r: usize, c: usize) -> Result<()> {
+        let (rows, cols) = self.size();
+        if r < rows && c < cols {
+            Ok(())
+        } else {
+            Err(Error::EIRANGE)
+        }
+    }
+
+    /// Zeroes every stored element, keeping the sparsity pattern.
+    pub fn clear(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::smatrix_clear(self.q) });
+        Ok(())
+    }
+
+    /// Removes every stored element.
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { T::smatrix_reset(self.q) });
+        Ok(())
+    }
+
+    /// Whether element `(r, c)` is stored.
+    pub fn is_set(&self, r: usize, c: usize) -> Result<bool> {
+        self.check_index(r, c)?;
+        Ok(unsafe { T::smatrix_isset(self.q, r as _, c as _) } != 0)
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> Result<T> {
+        self.check_index(r, c)?;
+        Ok(unsafe { T::smatrix_get(self.q, r as _, c as _) })
+    }
+
+    /// Sets element `(r, c)`, storing it if it was not already.
+    pub fn set(&mut self, r: usize, c: usize, v: T) -> Result<()> {
+        self.check_index(r, c)?;
+        check_values(&[v])?;
+        liquid_try!(unsafe { T::smatrix_set(self.q, r as _, c as _, v) });
+        Ok(())
+    }
+
+    /// Stores a new element at `(r, c)`.
+    pub fn insert(&mut self, r: usize, c: usize, v: T) -> Result<()> {
+        self.check_index(r, c)?;
+        check_values(&[v])?;
+        liquid_try!(unsafe { T::smatrix_insert(self.q, r as _, c as _, v) });
+        Ok(())
+    }
+
+    /// Removes element `(r, c)` from storage.
+    pub fn delete(&mut self, r: usize, c: usize) -> Result<()> {
+        self.check_index(r, c)?;
+        liquid_try!(unsafe { T::smatrix_delete(self.q, r as _, c as _) });
+        Ok(())
+    }
+
+    /// Matrix product; fails with [`Error::EIRANGE`] unless `self.cols() ==
+    /// rhs.rows()`.
+    pub fn mul(&self, rhs: &SparseMatrix<T>) -> Result<SparseMatrix<T>> {
+        let (m, k) = self.size();
+        let (ry, n) = rhs.size();
+        if k != ry {
+            return Err(Error::EIRANGE);
+        }
+        let z = SparseMatrix::create(m, n)?;
+        liquid_try!(unsafe { T::smatrix_mul(self.q, rhs.q, z.q) });
+        Ok(z)
+    }
+
+    /// Multiplies the column vector `x`; fails with [`Error::EIRANGE`] unless
+    /// `x` has `cols()` elements, and with [`Error::EIVAL`] on a non-binary
+    /// `u8` element.
+    pub fn vmul(&self, x: &[T]) -> Result<Vec<T>> {
+        let (rows, cols) = self.size();
+        if x.len() != cols {
+            return Err(Error::EIRANGE);
+        }
+        check_values(x)?;
+        let mut y = vec![T::default(); rows];
+        liquid_try!(unsafe { T::smatrix_vmul(self.q, x, &mut y) });
+        Ok(y)
+    }
+}
+
+impl<T: SparseElement<SMatrix = smatrixb_s>> SparseMatrix<T> {
+    /// Multiplies the dense `f32` matrix `x`, laid out row by row with
+    /// `x_cols` columns; fails with [`Error::EIRANGE`] on mismatched
+    /// dimensions.
+    pub fn mulf(&self, x: &[f32], x_cols: usize) -> Result<Vec<f32>> {
+        let (rows, cols) = self.size();
+        if x.len() != cols * x_cols {
+            return Err(Error::EIRANGE);
+        }
+        let mut y = vec![0.0; rows * x_cols];
+        liquid_try!(unsafe {
+            smatrixb_mulf(
+                self.q,
+                x.as_ptr() as *mut _,
+                cols as _,
+                x_cols as _,
+                y.as_mut_ptr(),
+                rows as _,
+                x_cols as _,
+            )
+        });
+        Ok(y)
+    }
+
+    /// Multiplies the `f32` column vector `x`; fails with [`Error::EIRANGE`]
+    /// unless `x` has `cols()` elements.
+    pub fn vmulf(&self, x: &[f32]) -> Result<Vec<f32>> {
+        let (rows, cols) = self.size();
+        if x.len() != cols {
+            return Err(Error::EIRANGE);
+        }
+        let mut y = vec![0.0; rows];
+        liquid_try!(unsafe { smatrixb_vmulf(self.q, x.as_ptr() as *mut _, y.as_mut_ptr()) });
+        Ok(y)
+    }
+}
+
+impl<T: SparseElement> Drop for SparseMatrix<T> {
+    fn drop(&mut self) {
+        unsafe { T::smatrix_destroy(self.q) };
+    }
+}
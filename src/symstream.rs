@@ -0,0 +1,440 @@
+//! Random symbol streams (`symstreamcf`/`symstreamrcf`), the `synth_crcf`
+//! PLL synthesizer and the `dds_cccf` digital up/down-converter.
+
+use liquidizers_sys::*;
+
+use crate::{created, Complex32, Error, FirFilterType, ModulationScheme, Result};
+
+/// Continuous stream of random, pulse-shaped symbols at an integer number of
+/// samples/symbol (`symstreamcf`).
+pub struct SymbolStream {
+    q: symstreamcf,
+}
+
+unsafe impl Send for SymbolStream {}
+
+impl SymbolStream {
+    /// Creates a stream with liquid's default parameters.
+    pub fn create() -> Result<SymbolStream> {
+        let q = created(unsafe { symstreamcf_create() })?;
+        Ok(SymbolStream { q })
+    }
+
+    /// Creates a stream of `ms` symbols shaped by `ftype` with `k`
+    /// samples/symbol, delay `m` symbols and excess bandwidth `beta`.
+    pub fn create_linear(
+        ftype: FirFilterType,
+        k: u32,
+        m: u32,
+        beta: f32,
+        ms: ModulationScheme,
+    ) -> Result<SymbolStream> {
+        let q = created(unsafe {
+            symstreamcf_create_linear(ftype.to_raw() as _, k, m, beta, ms.to_raw() as _)
+        })?;
+        Ok(SymbolStream { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { symstreamcf_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn ftype(&self) -> Option<FirFilterType> {
+        FirFilterType::from_raw(unsafe { symstreamcf_get_ftype(self.q) } as _)
+    }
+
+    /// Samples per symbol.
+    pub fn k(&self) -> f32 {
+        unsafe { symstreamcf_get_k(self.q) }
+    }
+
+    /// Filter delay in symbols.
+    pub fn m(&self) -> u32 {
+        unsafe { symstreamcf_get_m(self.q) }
+    }
+
+    pub fn beta(&self) -> f32 {
+        unsafe { symstreamcf_get_beta(self.q) }
+    }
+
+    pub fn scheme(&self) -> Option<ModulationScheme> {
+        ModulationScheme::from_raw(unsafe { symstreamcf_get_scheme(self.q) } as _)
+    }
+
+    pub fn set_scheme(&mut self, ms: ModulationScheme) -> Result<()> {
+        liquid_try!(unsafe { symstreamcf_set_scheme(self.q, ms.to_raw() as _) });
+        Ok(())
+    }
+
+    /// Linear output gain.
+    pub fn gain(&self) -> f32 {
+        unsafe { symstreamcf_get_gain(self.q) }
+    }
+
+    pub fn set_gain(&mut self, gain: f32) -> Result<()> {
+        liquid_try!(unsafe { symstreamcf_set_gain(self.q, gain) });
+        Ok(())
+    }
+
+    /// Delay of the pulse-shaping filter, in samples.
+    pub fn delay(&self) -> u32 {
+        unsafe { symstreamcf_get_delay(self.q) }
+    }
+
+    pub fn write_samples(&mut self, buf: &mut [Complex32]) -> Result<()> {
+        liquid_try!(unsafe { symstreamcf_write_samples(self.q, buf.as_mut_ptr(), buf.len() as _) });
+        Ok(())
+    }
+}
+
+impl Clone for SymbolStream {
+    fn clone(&self) -> Self {
+        SymbolStream {
+            q: unsafe { symstreamcf_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for SymbolStream {
+    fn drop(&mut self) {
+        unsafe { symstreamcf_destroy(self.q) };
+    }
+}
+
+/// Symbol stream with an arbitrary bandwidth, resampled from a
+/// [`SymbolStream`] (`symstreamrcf`).
+pub struct ResampledSymbolStream {
+    q: symstreamrcf,
+}
+
+unsafe impl Send for ResampledSymbolStream {}
+
+impl ResampledSymbolStream {
+    pub fn create() -> Result<ResampledSymbolStream> {
+        let q = created(unsafe { symstreamrcf_create() })?;
+        Ok(ResampledSymbolStream { q })
+    }
+
+    /// Creates a stream occupying normalized bandwidth `bw`.
+    pub fn create_linear(
+        ftype: FirFilterType,
+        bw: f32,
+        m: u32,
+        beta: f32,
+        ms: ModulationScheme,
+    ) -> Result<ResampledSymbolStream> {
+        let q = created(unsafe {
+            symstreamrcf_create_linear(ftype.to_raw() as _, bw, m, beta, ms.to_raw() as _)
+        })?;
+        Ok(ResampledSymbolStream { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { symstreamrcf_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn ftype(&self) -> Option<FirFilterType> {
+        FirFilterType::from_raw(unsafe { symstreamrcf_get_ftype(self.q) } as _)
+    }
+
+    pub fn bw(&self) -> f32 {
+        unsafe { symstreamrcf_get_bw(self.q) }
+    }
+
+    pub fn m(&self) -> u32 {
+        unsafe { symstreamrcf_get_m(self.q) }
+    }
+
+    pub fn beta(&self) -> f32 {
+        unsafe { symstreamrcf_get_beta(self.q) }
+    }
+
+    pub fn scheme(&self) -> Option<ModulationScheme> {
+        ModulationScheme::from_raw(unsafe { symstreamrcf_get_scheme(self.q) } as _)
+    }
+
+    pub fn set_scheme(&mut self, ms: ModulationScheme) -> Result<()> {
+        liquid_try!(unsafe { symstreamrcf_set_scheme(self.q, ms.to_raw() as _) });
+        Ok(())
+    }
+
+    pub fn gain(&self) -> f32 {
+        unsafe { symstreamrcf_get_gain(self.q) }
+    }
+
+    pub fn set_gain(&mut self, gain: f32) -> Result<()> {
+        liquid_try!(unsafe { symstreamrcf_set_gain(self.q, gain) });
+        Ok(())
+    }
+
+    /// Delay of the pulse-shaping and resampling filters, in samples.
+    pub fn delay(&self) -> f32 {
+        unsafe { symstreamrcf_get_delay(self.q) }
+    }
+
+    pub fn write_samples(&mut self, buf: &mut [Complex32]) -> Result<()> {
+        liquid_try!(unsafe {
+            symstreamrcf_write_samples(self.q, buf.as_mut_ptr(), buf.len() as _)
+        });
+        Ok(())
+    }
+}
+
+impl Clone for ResampledSymbolStream {
+    fn clone(&self) -> Self {
+        ResampledSymbolStream {
+            q: unsafe { symstreamrcf_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for ResampledSymbolStream {
+    fn drop(&mut self) {
+        unsafe { symstreamrcf_destroy(self.q) };
+    }
+}
+
+/// Table-driven synthesizer with a phase-locked loop, also used for spreading
+/// and despreading (`synth_crcf`).
+pub struct Synth {
+    q: synth_crcf,
+}
+
+unsafe impl Send for Synth {}
+
+impl Synth {
+    /// Creates a synthesizer from one period of its waveform.
+    pub fn create(table: &[Complex32]) -> Result<Synth> {
+        if table.is_empty() {
+            return Err(Error::EICONFIG);
+        }
+        let q = created(unsafe { synth_crcf_create(table.as_ptr(), table.len() as _) })?;
+        Ok(Synth { q })
+    }
+
+    pub fn reset(&mut self) {
+        unsafe { synth_crcf_reset(self.q) }
+    }
+
+    /// Number of entries in the waveform table.
+    pub fn len(&self) -> usize {
+        unsafe { synth_crcf_get_length(self.q) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn frequency(&self) -> f32 {
+        unsafe { synth_crcf_get_frequency(self.q) }
+    }
+
+    pub fn set_frequency(&mut self, f: f32) {
+        unsafe { synth_crcf_set_frequency(self.q, f) }
+    }
+
+    pub fn adjust_frequency(&mut self, df: f32) {
+        unsafe { synth_crcf_adjust_frequency(self.q, df) }
+    }
+
+    pub fn phase(&self) -> f32 {
+        unsafe { synth_crcf_get_phase(self.q) }
+    }
+
+    pub fn set_phase(&mut self, phi: f32) {
+        unsafe { synth_crcf_set_phase(self.q, phi) }
+    }
+
+    pub fn adjust_phase(&mut self, dphi: f32) {
+        unsafe { synth_crcf_adjust_phase(self.q, dphi) }
+    }
+
+    pub fn current(&self) -> Complex32 {
+        unsafe { synth_crcf_get_current(self.q) }
+    }
+
+    pub fn half_previous(&self) -> Complex32 {
+        unsafe { synth_crcf_get_half_previous(self.q) }
+    }
+
+    pub fn half_next(&self) -> Complex32 {
+        unsafe { synth_crcf_get_half_next(self.q) }
+    }
+
+    /// Advances the synthesizer by one sample.
+    pub fn step(&mut self) {
+        unsafe { synth_crcf_step(self.q) }
+    }
+
+    pub fn pll_set_bandwidth(&mut self, bandwidth: f32) {
+        unsafe { synth_crcf_pll_set_bandwidth(self.q, bandwidth) }
+    }
+
+    /// Updates the PLL with phase error `dphi`.
+    pub fn pll_step(&mut self, dphi: f32) {
+        unsafe { synth_crcf_pll_step(self.q, dphi) }
+    }
+
+    pub fn mix_up(&mut self, x: Complex32) -> Complex32 {
+        let mut y = Complex32::default();
+        unsafe { synth_crcf_mix_up(self.q, x, &mut y) };
+        y
+    }
+
+    pub fn mix_down(&mut self, x: Complex32) -> Complex32 {
+        let mut y = Complex32::default();
+        unsafe { synth_crcf_mix_down(self.q, x, &mut y) };
+        y
+    }
+
+    /// Mixes a block up, advancing the synthesizer after each sample.
+    pub fn mix_block_up(&mut self, x: &[Complex32]) -> Vec<Complex32> {
+        let mut y = vec![Complex32::default(); x.len()];
+        unsafe {
+            synth_crcf_mix_block_up(self.q, x.as_ptr() as *mut _, y.as_mut_ptr(), x.len() as _)
+        };
+        y
+    }
+
+    /// Mixes a block down, advancing the synthesizer after each sample.
+    pub fn mix_block_down(&mut self, x: &[Complex32]) -> Vec<Complex32> {
+        let mut y = vec![Complex32::default(); x.len()];
+        unsafe {
+            synth_crcf_mix_block_down(self.q, x.as_ptr() as *mut _, y.as_mut_ptr(), x.len() as _)
+        };
+        y
+    }
+
+    /// Spreads one symbol over `len()` chips.
+    pub fn spread(&mut self, x: Complex32) -> Vec<Complex32> {
+        let mut y = vec![Complex32::default(); self.len()];
+        unsafe { synth_crcf_spread(self.q, x, y.as_mut_ptr()) };
+        y
+    }
+
+    /// Despreads `len()` chips into one symbol; fails with
+    /// [`Error::EIRANGE`] unless `x` is `len()` chips.
+    pub fn despread(&mut self, x: &[Complex32]) -> Result<Complex32> {
+        if x.len() != self.len() {
+            return Err(Error::EIRANGE);
+        }
+        let mut y = Complex32::default();
+        unsafe { synth_crcf_despread(self.q, x.as_ptr() as *mut _, &mut y) };
+        Ok(y)
+    }
+
+    /// Despreads `len()` chips, returning the early, punctual and late
+    /// correlations; fails with [`Error::EIRANGE`] unless `x` is `len()` chips.
+    pub fn despread_triple(
+        &mut self,
+        x: &[Complex32],
+    ) -> Result<(Complex32, Complex32, Complex32)> {
+        if x.len() != self.len() {
+            return Err(Error::EIRANGE);
+        }
+        let mut early = Complex32::default();
+        let mut punctual = Complex32::default();
+        let mut late = Complex32::default();
+        unsafe {
+            synth_crcf_despread_triple(
+                self.q,
+                x.as_ptr() as *mut _,
+                &mut early,
+                &mut punctual,
+                &mut late,
+            )
+        };
+        Ok((early, punctual, late))
+    }
+}
+
+impl Drop for Synth {
+    fn drop(&mut self) {
+        unsafe { synth_crcf_destroy(self.q) };
+    }
+}
+
+/// Direct digital synthesizer: a cascade of half-band stages with a mixer,
+/// interpolating up to or decimating down from carrier `fc` (`dds_cccf`).
+pub struct Dds {
+    q: dds_cccf,
+}
+
+unsafe impl Send for Dds {}
+
+impl Dds {
+    /// Creates a synthesizer with `num_stages` half-band stages (a rate change
+    /// of `2^num_stages`), carrier `fc`, bandwidth `bw` and stop-band
+    /// attenuation `attenuation` dB.
+    pub fn create(num_stages: u32, fc: f32, bw: f32, attenuation: f32) -> Result<Dds> {
+        let q = created(unsafe { dds_cccf_create(num_stages, fc, bw, attenuation) })?;
+        Ok(Dds { q })
+    }
+
+    pub fn reset(&mut self) -> Result<()> {
+        liquid_try!(unsafe { dds_cccf_reset(self.q) });
+        Ok(())
+    }
+
+    pub fn scale(&self) -> Complex32 {
+        let mut scale = Complex32::default();
+        unsafe { dds_cccf_get_scale(self.q, &mut scale) };
+        scale
+    }
+
+    pub fn set_scale(&mut self, scale: Complex32) -> Result<()> {
+        liquid_try!(unsafe { dds_cccf_set_scale(self.q, scale) });
+        Ok(())
+    }
+
+    pub fn num_stages(&self) -> u32 {
+        unsafe { dds_cccf_get_num_stages(self.q) }
+    }
+
+    /// Rate change, `2^num_stages()`.
+    pub fn rate(&self) -> usize {
+        1 << self.num_stages()
+    }
+
+    pub fn delay_interp(&self) -> u32 {
+        unsafe { dds_cccf_get_delay_interp(self.q) }
+    }
+
+    pub fn delay_decim(&self) -> f32 {
+        unsafe { dds_cccf_get_delay_decim(self.q) }
+    }
+
+    /// Mixes down and decimates `rate()` samples into one; fails with
+    /// [`Error::EIRANGE`] unless `x` is `rate()` samples.
+    pub fn decim_execute(&mut self, x: &[Complex32]) -> Result<Complex32> {
+        if x.len() != self.rate() {
+            return Err(Error::EIRANGE);
+        }
+        let mut y = Complex32::default();
+        liquid_try!(unsafe { dds_cccf_decim_execute(self.q, x.as_ptr() as *mut _, &mut y) });
+        Ok(y)
+    }
+
+    /// Interpolates one sample into `rate()` samples mixed up to the carrier.
+    pub fn interp_execute(&mut self, x: Complex32) -> Result<Vec<Complex32>> {
+        let mut y = vec![Complex32::default(); self.rate()];
+        liquid_try!(unsafe { dds_cccf_interp_execute(self.q, x, y.as_mut_ptr()) });
+        Ok(y)
+    }
+}
+
+impl Clone for Dds {
+    fn clone(&self) -> Self {
+        Dds {
+            q: unsafe { dds_cccf_copy(self.q) },
+        }
+    }
+}
+
+impl Drop for Dds {
+    fn drop(&mut self) {
+        unsafe { dds_cccf_destroy(self.q) };
+    }
+}
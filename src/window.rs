@@ -0,0 +1,129 @@
+//! Spectral windowing functions (`liquid_window_type`).
+
+use liquidizers_sys::*;
+
+use std::f32::consts::PI;
+use std::ffi::CString;
+use std::str::FromStr;
+
+use crate::{static_str, Error, Result};
+
+liquid_enum! {
+    /// Window shape (`liquid_window_type`).
+    pub enum WindowType: liquid_window_type {
+        Hamming = liquid_window_type_LIQUID_WINDOW_HAMMING,
+        Hann = liquid_window_type_LIQUID_WINDOW_HANN,
+        BlackmanHarris = liquid_window_type_LIQUID_WINDOW_BLACKMANHARRIS,
+        BlackmanHarris7 = liquid_window_type_LIQUID_WINDOW_BLACKMANHARRIS7,
+        Kaiser = liquid_window_type_LIQUID_WINDOW_KAISER,
+        Flattop = liquid_window_type_LIQUID_WINDOW_FLATTOP,
+        Triangular = liquid_window_type_LIQUID_WINDOW_TRIANGULAR,
+        RcosTaper = liquid_window_type_LIQUID_WINDOW_RCOSTAPER,
+        Kbd = liquid_window_type_LIQUID_WINDOW_KBD,
+    }
+}
+
+impl WindowType {
+    /// Short name used by liquid, e.g. `"blackmanharris"`.
+    pub fn name(self) -> &'static str {
+        unsafe { static_str(liquid_window_str[self.to_raw() as usize][0]) }
+    }
+
+    /// Human-readable name, e.g. `"Blackman-harris (4-term)"`.
+    pub fn description(self) -> &'static str {
+        unsafe { static_str(liquid_window_str[self.to_raw() as usize][1]) }
+    }
+
+    /// Generates a window of `len` taps. `param` is ignored except for:
+    ///
+    /// - [`Kaiser`](Self::Kaiser) and [`Kbd`](Self::Kbd): the shape factor
+    ///   `beta`, at least 0;
+    /// - [`RcosTaper`](Self::RcosTaper): the taper length, a whole number in
+    ///   `0..=len/2`;
+    /// - [`Triangular`](Self::Triangular): the sub-length `n`, a whole number
+    ///   in `len-1..=len+1` and at least 1.
+    ///
+    /// Fails with [`Error::EIRANGE`] if `len` is zero or `param` is outside
+    /// these ranges, which liquid would otherwise answer with all-zero taps.
+    pub fn generate(self, len: usize, param: f32) -> Result<Vec<f32>> {
+        if len == 0 || !self.param_valid(len, param) {
+            return Err(Error::EIRANGE);
+        }
+        let mut w = vec![0.0; len];
+        match self {
+            WindowType::Kbd => {
+                liquid_try!(unsafe { liquid_kbd_window(len as _, param, w.as_mut_ptr()) });
+            }
+            WindowType::Kaiser => {
+                for (i, w) in w.iter_mut().enumerate() {
+                    *w = unsafe { liquid_kaiser(i as _, len as _, param) };
+                }
+            }
+            _ => {
+                for (i, w) in w.iter_mut().enumerate() {
+                    *w = unsafe { liquid_windowf(self.to_raw(), i as _, len as _, param) };
+                }
+            }
+        }
+        Ok(w)
+    }
+
+    fn param_valid(self, len: usize, param: f32) -> bool {
+        let whole = param.is_finite() && param >= 0.0 && param.fract() == 0.0;
+        match self {
+            WindowType::Kaiser | WindowType::Kbd => param >= 0.0,
+            WindowType::RcosTaper => whole && param as usize <= len / 2,
+            WindowType::Triangular => {
+                let n = param as usize;
+                whole && n >= 1 && n + 1 >= len && n <= len + 1
+            }
+            _ => true,
+        }
+    }
+
+    /// Metrics of the window [`generate`](Self::generate) would return.
+    pub fn metrics(self, len: usize, param: f32) -> Result<WindowMetrics> {
+        Ok(WindowMetrics::from_window(&self.generate(len, param)?))
+    }
+}
+
+impl FromStr for WindowType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Error> {
+        let s = CString::new(s).map_err(|_| Error::EIVAL)?;
+        let raw = unsafe { liquid_getopt_str2window(s.as_ptr()) };
+        WindowType::from_raw(raw).ok_or(Error::EIVAL)
+    }
+}
+
+/// Figures for calibrating spectral estimates made with a window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindowMetrics {
+    /// Mean tap value; a tone's amplitude is scaled by this much.
+    pub coherent_gain: f32,
+    /// Equivalent noise bandwidth in bins; divide power spectral densities by
+    /// this to recover the noise floor.
+    pub enbw: f32,
+    /// Loss in dB for a tone halfway between two bins.
+    pub scalloping_loss_db: f32,
+}
+
+impl WindowMetrics {
+    /// Computes the metrics of the taps `w`.
+    pub fn from_window(w: &[f32]) -> WindowMetrics {
+        let n = w.len() as f32;
+        let sum: f32 = w.iter().sum();
+        let sum_sq: f32 = w.iter().map(|w| w * w).sum();
+        // response at half a bin, relative to DC
+        let (re, im) = w.iter().enumerate().fold((0.0, 0.0), |(re, im), (i, w)| {
+            let theta = PI * i as f32 / n;
+            (re + w * theta.cos(), im - w * theta.sin())
+        });
+        WindowMetrics {
+            coherent_gain: sum / n,
+            enbw: n * sum_sq / (sum * sum),
+            scalloping_loss_db: -20.0 * ((re * re + im * im).sqrt() / sum).log10(),
+        }
+    }
+}